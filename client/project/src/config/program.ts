@@ -0,0 +1,75 @@
+// Commcoin program id, instruction encoding and the global config fields the hooks need.
+// Layouts follow src/idls/commcoin.json, regenerate it whenever the program changes.
+import { Connection, PublicKey } from '@solana/web3.js';
+import { Buffer } from 'buffer';
+import commcoinIdl from '@/idls/commcoin.json';
+import { findConfigPda } from '@/config/seeds';
+
+export const PROGRAM_ID = new PublicKey(commcoinIdl.address);
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+// Anchor takes the program id in the slot of an optional account that is left out
+export const NONE_ACCOUNT = PROGRAM_ID;
+
+// --- Borsh encoding of instruction args ---
+
+export const encodeU8 = (value: number) => Buffer.from([value]);
+
+export const encodeU64 = (value: bigint | number) => {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value));
+    return buf;
+};
+
+export const encodeString = (value: string) => {
+    const bytes = Buffer.from(value, 'utf8');
+    return Buffer.concat([encodeU32(bytes.length), bytes]);
+};
+
+export const encodeOption = <T>(value: T | null | undefined, encode: (v: T) => Buffer) =>
+    value === null || value === undefined ? encodeU8(0) : Buffer.concat([encodeU8(1), encode(value)]);
+
+// Merkle proof of the launch allow list, empty outside of it
+export const encodeProof = (proof: Uint8Array[] = []) =>
+    Buffer.concat([encodeU32(proof.length), ...proof.map((node) => Buffer.from(node))]);
+
+function encodeU32(value: number) {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(value);
+    return buf;
+}
+
+export function encodeInstructionData(method: string, args: Buffer[] = []): Buffer {
+    const ix = commcoinIdl.instructions.find((i: any) => i.name === method);
+    if (!ix) throw new Error(`Method ${method} not found in IDL`);
+    return Buffer.concat([Buffer.from(ix.discriminator), ...args]);
+}
+
+// --- Global config ---
+
+// Byte offsets in the Config account: discriminator, version, then authority, pending_authority, fee_recipient
+const FEE_RECIPIENT_OFFSET = 8 + 1 + 32 * 2;
+// curve_limit and the three initial reserves come before total_token_supply
+const TOTAL_TOKEN_SUPPLY_OFFSET = FEE_RECIPIENT_OFFSET + 32 + 8 * 4;
+// then the seven fee settings, allowed_curve_types, two CurveParams and LaunchBounds
+const PROTECTED_SLOTS_OFFSET = TOTAL_TOKEN_SUPPLY_OFFSET + 8 + 2 * 7 + 1 + 20 * 2 + 37;
+
+export interface GlobalConfig {
+    address: PublicKey;
+    feeRecipient: PublicKey;
+    totalTokenSupply: bigint; // default supply of a new coin, base units
+    protectedSlots: bigint;   // default anti-snipe window of a new coin, 0 when there is none
+}
+
+export async function fetchGlobalConfig(connection: Connection): Promise<GlobalConfig> {
+    const address = findConfigPda(PROGRAM_ID);
+    const info = await connection.getAccountInfo(address);
+    if (!info) throw new Error('Commcoin config account not found');
+    const data = Buffer.from(info.data);
+    return {
+        address,
+        feeRecipient: new PublicKey(data.subarray(FEE_RECIPIENT_OFFSET, FEE_RECIPIENT_OFFSET + 32)),
+        totalTokenSupply: data.readBigUInt64LE(TOTAL_TOKEN_SUPPLY_OFFSET),
+        protectedSlots: data.readBigUInt64LE(PROTECTED_SLOTS_OFFSET),
+    };
+}
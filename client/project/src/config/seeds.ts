@@ -0,0 +1,55 @@
+// PDA seeds of the commcoin program.
+// Mirrors contracts/commcoin/programs/commcoin/src/seeds.rs, the same values are exported as IDL constants.
+import { PublicKey } from '@solana/web3.js';
+import { Buffer } from 'buffer';
+
+export const CONFIG_SEED = Buffer.from('config_v1');
+export const MINT_AUTHORITY_SEED = Buffer.from('mint_authority_v1');
+export const BONDING_CURVE_SEED = Buffer.from('commcoin_bonding_curve');
+export const VAULT_CONFIG_SEED = Buffer.from('vault_config_v1');
+export const VAULT_TOKEN_SEED = Buffer.from('vault_tokens_v1');
+export const USER_STAKE_INFO_SEED = Buffer.from('user_stake_v1');
+export const BUYER_RECORD_SEED = Buffer.from('buyer_record_v1');
+export const VAULT_MILESTONES_SEED = Buffer.from('vault_milestones_v1');
+export const PROPOSAL_SEED = Buffer.from('proposal_v1');
+export const VOTE_RECORD_SEED = Buffer.from('vote_record_v1');
+export const CREATOR_VESTING_SEED = Buffer.from('creator_vesting_v1');
+
+const pda = (seeds: (Buffer | Uint8Array)[], programId: PublicKey) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+export const findConfigPda = (programId: PublicKey) =>
+    pda([CONFIG_SEED], programId);
+
+export const findMintAuthorityPda = (mint: PublicKey, programId: PublicKey) =>
+    pda([MINT_AUTHORITY_SEED, mint.toBuffer()], programId);
+
+export const findBondingCurvePda = (mint: PublicKey, programId: PublicKey) =>
+    pda([BONDING_CURVE_SEED, mint.toBuffer()], programId);
+
+export const findVaultConfigPda = (mint: PublicKey, programId: PublicKey) =>
+    pda([VAULT_CONFIG_SEED, mint.toBuffer()], programId);
+
+export const findVaultTokenPda = (mint: PublicKey, programId: PublicKey) =>
+    pda([VAULT_TOKEN_SEED, mint.toBuffer()], programId);
+
+export const findUserStakeInfoPda = (user: PublicKey, vault: PublicKey, programId: PublicKey) =>
+    pda([USER_STAKE_INFO_SEED, user.toBuffer(), vault.toBuffer()], programId);
+
+export const findBuyerRecordPda = (bondingCurve: PublicKey, user: PublicKey, programId: PublicKey) =>
+    pda([BUYER_RECORD_SEED, bondingCurve.toBuffer(), user.toBuffer()], programId);
+
+export const findVaultMilestonesPda = (vault: PublicKey, programId: PublicKey) =>
+    pda([VAULT_MILESTONES_SEED, vault.toBuffer()], programId);
+
+export const findProposalPda = (vault: PublicKey, id: bigint | number, programId: PublicKey) => {
+    const idBytes = Buffer.alloc(8);
+    idBytes.writeBigUInt64LE(BigInt(id));
+    return pda([PROPOSAL_SEED, vault.toBuffer(), idBytes], programId);
+};
+
+export const findVoteRecordPda = (proposal: PublicKey, voter: PublicKey, programId: PublicKey) =>
+    pda([VOTE_RECORD_SEED, proposal.toBuffer(), voter.toBuffer()], programId);
+
+export const findCreatorVestingPda = (mint: PublicKey, programId: PublicKey) =>
+    pda([CREATOR_VESTING_SEED, mint.toBuffer()], programId);
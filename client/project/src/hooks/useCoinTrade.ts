@@ -1,6 +1,7 @@
 import { useWallet, useConnection } from '@solana/wallet-adapter-react';
 import { useState, useCallback } from 'react';
 import {
+    Connection,
     PublicKey,
     Transaction,
     SystemProgram,
@@ -9,14 +10,62 @@ import {
 } from '@solana/web3.js';
 import {
     createAssociatedTokenAccountIdempotentInstruction,
-    getAssociatedTokenAddress,
-    TOKEN_PROGRAM_ID,
-    ASSOCIATED_TOKEN_PROGRAM_ID
+    getAssociatedTokenAddressSync,
+    getMint
 } from '@solana/spl-token';
-import commcoinIdl from '@/idls/commcoin.json';
-import { Buffer } from 'buffer';
-
-const PROGRAM_ID = new PublicKey(commcoinIdl.address);
+import { findBondingCurvePda, findBuyerRecordPda, findVaultConfigPda } from '@/config/seeds';
+import {
+    PROGRAM_ID,
+    NONE_ACCOUNT,
+    encodeInstructionData,
+    encodeProof,
+    encodeU64,
+    fetchGlobalConfig,
+} from '@/config/program';
+
+// Room left under the displayed price for fees and price movement, in basis points
+const SLIPPAGE_BPS = 1_000;
+const LAMPORTS_PER_SOL = 1_000_000_000;
+const DEFAULT_PRICE = 2.92508282e-8; // SOL per token at the start of the default curve
+
+const withSlippage = (amount: number) => BigInt(Math.floor(amount * (10_000 - SLIPPAGE_BPS) / 10_000));
+
+// Accounts shared by buy and sell, in IDL order up to the vault.
+// The coin can be a classic SPL or a Token-2022 mint, the token program is its owner.
+async function tradeAccounts(connection: Connection, user: PublicKey, mint: PublicKey) {
+    const mintInfo = await connection.getAccountInfo(mint);
+    if (!mintInfo) throw new Error('Mint not found');
+    const tokenProgram = mintInfo.owner;
+    const { decimals } = await getMint(connection, mint, 'confirmed', tokenProgram);
+
+    const config = await fetchGlobalConfig(connection);
+    const bondingCurve = findBondingCurvePda(mint, PROGRAM_ID);
+    const bondingCurveTokenVault = getAssociatedTokenAddressSync(mint, bondingCurve, true, tokenProgram);
+    const userAta = getAssociatedTokenAddressSync(mint, user, false, tokenProgram);
+    const vault = findVaultConfigPda(mint, PROGRAM_ID);
+
+    const keys = [
+        { pubkey: user, isSigner: true, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: bondingCurve, isSigner: false, isWritable: true },
+        { pubkey: bondingCurveTokenVault, isSigner: false, isWritable: true },
+        { pubkey: userAta, isSigner: false, isWritable: true },
+        { pubkey: config.address, isSigner: false, isWritable: false },
+        { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: vault, isSigner: false, isWritable: true },
+    ];
+    // Created if missing, the program expects the user's ATA to exist
+    const ataIx = createAssociatedTokenAccountIdempotentInstruction(user, userAta, user, mint, tokenProgram);
+    return { keys, ataIx, bondingCurve, tokenProgram, decimals };
+}
+
+// Program accounts closing the buy and sell account lists
+const tailKeys = (tokenProgram: PublicKey) => [
+    { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+];
 
 export const useCoinTrade = () => {
     const { publicKey, signTransaction } = useWallet();
@@ -34,45 +83,27 @@ export const useCoinTrade = () => {
             if (!publicKey || !signTransaction) throw new Error('Wallet not connected');
             const mintPubkey = new PublicKey(mint);
 
-            // Derive PDAs
-            const [bondingCurve] = PublicKey.findProgramAddressSync(
-                [Buffer.from('commcoin_bonding_curve'), mintPubkey.toBuffer()],
-                PROGRAM_ID
-            );
-            const bondingCurveTokenVault = await getAssociatedTokenAddress(mintPubkey, bondingCurve, true);
-            const userAta = await getAssociatedTokenAddress(mintPubkey, publicKey);
-
-            // Create ATA if needed
-            const ataIx = createAssociatedTokenAccountIdempotentInstruction(publicKey, userAta, publicKey, mintPubkey);
-
-            // Calculate token amount to buy
-            const initial_token_cost_in_sol_per_token = price > 0 ? price : 2.92508282e-8;
-            const amount_used_in_sol = amountSol;
-            const lamports_per_sol = 1000000000;
-            const amount_sol_used_for_tokens = amount_used_in_sol - amount_used_in_sol * 0.1;
-            const token_to_give = amount_sol_used_for_tokens / initial_token_cost_in_sol_per_token;
-
-            // Prepare buy instruction
-            const buyData = Buffer.alloc(16); // 8 bytes for amount, 8 for maxSolCost
-            buyData.writeBigUInt64LE(BigInt(Math.floor(token_to_give * 1e6)), 0);
-            buyData.writeBigUInt64LE(BigInt(Math.floor(lamports_per_sol * amount_used_in_sol)), 8);
-            const discriminator = Buffer.from(commcoinIdl.instructions.find((i: any) => i.name === 'buy')!.discriminator);
-            const finalBuyData = Buffer.concat([discriminator, buyData]);
+            const { keys, ataIx, bondingCurve, tokenProgram, decimals } =
+                await tradeAccounts(connection, publicKey, mintPubkey);
+
+            // Spends exactly amountSol, fees included, and expects at least the tokens the price shows less slippage
+            const tokenPrice = price > 0 ? price : DEFAULT_PRICE;
+            const solIn = BigInt(Math.floor(amountSol * LAMPORTS_PER_SOL));
+            const minTokensOut = withSlippage(amountSol / tokenPrice * 10 ** decimals);
+
+            // The buyer record is only required in the protected launch window, passing it
+            // always keeps early buys working at the cost of the record's rent
+            const buyerRecord = findBuyerRecordPda(bondingCurve, publicKey, PROGRAM_ID);
 
             const buyIx = new TransactionInstruction({
                 programId: PROGRAM_ID,
                 keys: [
-                    { pubkey: publicKey, isSigner: true, isWritable: true },
-                    { pubkey: mintPubkey, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurve, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurveTokenVault, isSigner: false, isWritable: true },
-                    { pubkey: userAta, isSigner: false, isWritable: true },
-                    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-                    { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+                    ...keys,
+                    { pubkey: buyerRecord, isSigner: false, isWritable: true },
+                    { pubkey: NONE_ACCOUNT, isSigner: false, isWritable: false }, // referrer
+                    ...tailKeys(tokenProgram),
                 ],
-                data: finalBuyData,
+                data: encodeInstructionData('buy_exact_sol_in', [encodeU64(solIn), encodeU64(minTokensOut), encodeProof()]),
             });
 
             const tx = new Transaction().add(ataIx, buyIx);
@@ -103,41 +134,21 @@ export const useCoinTrade = () => {
             if (!publicKey || !signTransaction) throw new Error('Wallet not connected');
             const mintPubkey = new PublicKey(mint);
 
-            // Derive PDAs
-            const [bondingCurve] = PublicKey.findProgramAddressSync(
-                [Buffer.from('commcoin_bonding_curve'), mintPubkey.toBuffer()],
-                PROGRAM_ID
-            );
-            const bondingCurveTokenVault = await getAssociatedTokenAddress(mintPubkey, bondingCurve, true);
-            const userAta = await getAssociatedTokenAddress(mintPubkey, publicKey);
-
-            // Create ATA if needed
-            const ataIx = createAssociatedTokenAccountIdempotentInstruction(publicKey, userAta, publicKey, mintPubkey);
-
-            // Prepare sell instruction
-            const lamports_per_sol = 1000000000;
-            const initial_token_cost_in_sol_per_token = price > 0 ? price : 2.92508282e-8;
-            const sol_to_receive = amountTokens * initial_token_cost_in_sol_per_token;
-            const sellData = Buffer.alloc(16); // 8 bytes for amount, 8 for maxSolCost
-            sellData.writeBigUInt64LE(BigInt(Math.floor(amountTokens * 1e6)), 0);
-            sellData.writeBigUInt64LE(BigInt(Math.floor(lamports_per_sol * sol_to_receive)), 8);
-            const discriminator = Buffer.from(commcoinIdl.instructions.find((i: any) => i.name === 'sell')!.discriminator);
-            const finalSellData = Buffer.concat([discriminator, sellData]);
+            const { keys, ataIx, tokenProgram, decimals } = await tradeAccounts(connection, publicKey, mintPubkey);
+
+            // Sells exactly amountTokens, for at least what the price shows less slippage
+            const tokenPrice = price > 0 ? price : DEFAULT_PRICE;
+            const amount = BigInt(Math.floor(amountTokens * 10 ** decimals));
+            const minSolOutput = withSlippage(amountTokens * tokenPrice * LAMPORTS_PER_SOL);
 
             const sellIx = new TransactionInstruction({
                 programId: PROGRAM_ID,
                 keys: [
-                    { pubkey: publicKey, isSigner: true, isWritable: true },
-                    { pubkey: mintPubkey, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurve, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurveTokenVault, isSigner: false, isWritable: true },
-                    { pubkey: userAta, isSigner: false, isWritable: true },
-                    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-                    { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+                    ...keys,
+                    { pubkey: NONE_ACCOUNT, isSigner: false, isWritable: false }, // referrer
+                    ...tailKeys(tokenProgram),
                 ],
-                data: finalSellData,
+                data: encodeInstructionData('sell', [encodeU64(amount), encodeU64(minSolOutput)]),
             });
 
             const tx = new Transaction().add(ataIx, sellIx);
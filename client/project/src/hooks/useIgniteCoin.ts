@@ -7,18 +7,54 @@ import {
     Connection,
     SystemProgram,
     SYSVAR_RENT_PUBKEY,
-    TransactionInstruction
+    TransactionInstruction,
+    ComputeBudgetProgram
 } from '@solana/web3.js';
 import {
-    createAssociatedTokenAccountIdempotentInstruction,
-    getAssociatedTokenAddress,
+    getAssociatedTokenAddressSync,
     TOKEN_PROGRAM_ID,
     ASSOCIATED_TOKEN_PROGRAM_ID
 } from '@solana/spl-token';
-import commcoinIdl from '@/idls/commcoin.json';
-
-
-const PROGRAM_ID = new PublicKey(commcoinIdl.address);
+import { Buffer } from 'buffer';
+import {
+    findBondingCurvePda,
+    findBuyerRecordPda,
+    findMintAuthorityPda,
+    findVaultConfigPda,
+    findVaultTokenPda,
+} from '@/config/seeds';
+import {
+    PROGRAM_ID,
+    TOKEN_METADATA_PROGRAM_ID,
+    NONE_ACCOUNT,
+    encodeInstructionData,
+    encodeOption,
+    encodeString,
+    encodeU64,
+    encodeU8,
+    fetchGlobalConfig,
+} from '@/config/program';
+
+// Token metadata program limits, create_coin rejects longer values
+const MAX_NAME_LENGTH = 32;
+const MAX_SYMBOL_LENGTH = 10;
+const MAX_URI_LENGTH = 200;
+const MAX_VAULT_NAME_LENGTH = 50;
+
+// Vault goal when none is given, share of the supply in basis points
+const DEFAULT_VAULT_GOAL_BPS = 1_000n;
+
+// CurveType variant index in the IDL
+const CURVE_CONSTANT_PRODUCT = 0;
+
+// create_coin inits the mint, metadata, vault and curve accounts and runs the initial buy
+const CREATE_COIN_COMPUTE_UNITS = 400_000;
+
+const truncate = (value: string, maxBytes: number) => {
+    let out = value;
+    while (Buffer.byteLength(out, 'utf8') > maxBytes) out = out.slice(0, -1);
+    return out;
+};
 
 /**
  * Derives a PDA address and fetches its account info from the network.
@@ -41,6 +77,7 @@ export interface IgniteCoinParams {
     description: string;
     symbol: string;
     iconUrl?: string;
+    vaultGoal?: bigint; // tokens in base units, DEFAULT_VAULT_GOAL_BPS of the supply when not set
 }
 
 export const useIgniteCoin = () => {
@@ -60,87 +97,75 @@ export const useIgniteCoin = () => {
             const mintKeypair = Keypair.generate();
             const mint = mintKeypair.publicKey;
 
+            const config = await fetchGlobalConfig(connection);
+
             // --- Derive all required PDAs ---
-            const [mintAuthority] = PublicKey.findProgramAddressSync(
-                [Buffer.from("commcoin_bonding_curve"), Buffer.from("commcoin_random_minted")],
-                PROGRAM_ID
-            );
-            const [bondingCurve] = PublicKey.findProgramAddressSync(
-                [Buffer.from("commcoin_bonding_curve"), mint.toBuffer()],
-                PROGRAM_ID
-            );
-            const [vault] = PublicKey.findProgramAddressSync(
-                [Buffer.from("vault_config_v1"), mint.toBuffer()],
-                PROGRAM_ID
+            const mintAuthority = findMintAuthorityPda(mint, PROGRAM_ID);
+            const bondingCurve = findBondingCurvePda(mint, PROGRAM_ID);
+            const vault = findVaultConfigPda(mint, PROGRAM_ID);
+            const vaultTokenAccount = findVaultTokenPda(mint, PROGRAM_ID);
+            const [metadata] = PublicKey.findProgramAddressSync(
+                [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+                TOKEN_METADATA_PROGRAM_ID
             );
-            const [vaultTokenAccount] = PublicKey.findProgramAddressSync(
-                [Buffer.from("vault_tokens_v1"), mint.toBuffer()],
-                PROGRAM_ID
-            );
-            const bondingCurveTokenVault = await getAssociatedTokenAddress(mint, bondingCurve, true);
-            const userAta = await getAssociatedTokenAddress(mint, publicKey);
+            const bondingCurveTokenVault = getAssociatedTokenAddressSync(mint, bondingCurve, true);
+            const creatorTokenAccount = getAssociatedTokenAddressSync(mint, publicKey);
+            const creatorBuyerRecord = findBuyerRecordPda(bondingCurve, publicKey, PROGRAM_ID);
 
             // --- Build Instructions ---
 
+            // The initial buy is part of create_coin, fees included, the program creates the creator ATA for it
+            const initialBuyLamports = BigInt(Math.floor(params.amountSol * 1_000_000_000));
+            const hasInitialBuy = initialBuyLamports > 0n;
+            // Inside the default launch window the initial buy counts toward the creator's wallet cap
+            const recordsInitialBuy = hasInitialBuy && config.protectedSlots > 0n;
+            const vaultGoal = params.vaultGoal ?? config.totalTokenSupply * DEFAULT_VAULT_GOAL_BPS / 10_000n;
+
+            // CreateCoinArgs, launch params, anti-snipe, schedule, allow list and vesting use the defaults
+            const args = Buffer.concat([
+                encodeString(truncate(params.title, MAX_NAME_LENGTH)),
+                encodeString(truncate(params.symbol, MAX_SYMBOL_LENGTH)),
+                encodeString(truncate(params.iconUrl ?? '', MAX_URI_LENGTH)),
+                encodeString(truncate(params.title, MAX_VAULT_NAME_LENGTH)),
+                encodeU64(vaultGoal),
+                encodeU8(CURVE_CONSTANT_PRODUCT),
+                encodeOption(null, encodeU8),                            // launch_params
+                encodeOption(hasInitialBuy ? initialBuyLamports : null, encodeU64), // initial_buy_sol
+                encodeOption(null, encodeU8),                            // anti_snipe
+                encodeOption(null, encodeU8),                            // trading_starts_at
+                encodeOption(null, encodeU8),                            // allow_list
+                encodeOption(null, encodeU8),                            // creator_vesting
+            ]);
+
             const createCoinIx = new TransactionInstruction({
                 programId: PROGRAM_ID,
                 keys: [
                     { pubkey: publicKey, isSigner: true, isWritable: true }, // signer
-                    { pubkey: mintAuthority, isSigner: false, isWritable: true },
+                    { pubkey: config.address, isSigner: false, isWritable: false },
+                    { pubkey: mintAuthority, isSigner: false, isWritable: false },
                     { pubkey: mint, isSigner: true, isWritable: true },
+                    { pubkey: metadata, isSigner: false, isWritable: true },
                     { pubkey: vault, isSigner: false, isWritable: true },
                     { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
                     { pubkey: bondingCurve, isSigner: false, isWritable: true },
                     { pubkey: bondingCurveTokenVault, isSigner: false, isWritable: true },
+                    { pubkey: NONE_ACCOUNT, isSigner: false, isWritable: false }, // creator_vesting
+                    { pubkey: NONE_ACCOUNT, isSigner: false, isWritable: false }, // vesting_token_account
+                    { pubkey: hasInitialBuy ? creatorTokenAccount : NONE_ACCOUNT, isSigner: false, isWritable: hasInitialBuy },
+                    { pubkey: recordsInitialBuy ? creatorBuyerRecord : NONE_ACCOUNT, isSigner: false, isWritable: recordsInitialBuy },
+                    { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
                     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+                    { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
                     { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
                     { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
                     { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
                 ],
-                data: Buffer.from(commcoinIdl.instructions.find((i: any) => i.name === 'create_coin')!.discriminator),
+                data: encodeInstructionData('create_coin', [args]),
             });
 
-            const extendAccountIx = new TransactionInstruction({
-                programId: PROGRAM_ID,
-                keys: [
-                    { pubkey: publicKey, isSigner: true, isWritable: true }, // user
-                    { pubkey: bondingCurve, isSigner: false, isWritable: true }, // curve_account
-                    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-                ],
-                data: Buffer.from(commcoinIdl.instructions.find((i: any) => i.name === 'extend_account')!.discriminator),
-            });
-
-            const ataIx = createAssociatedTokenAccountIdempotentInstruction(publicKey, userAta, publicKey, mint);
-
-            const initial_token_cost_in_sol_per_token = 2.92508282e-8;
-            const amount_used_in_sol = params.amountSol;
-            const lamports_per_sol = 1000000000;
-            const amount_sol_used_for_tokens = amount_used_in_sol - amount_used_in_sol * 0.1;
-            const token_to_give = amount_sol_used_for_tokens / initial_token_cost_in_sol_per_token;
-
-            const buyData = Buffer.alloc(16); // 8 bytes for amount, 8 for maxSolCost
-            buyData.writeBigUInt64LE(BigInt(Math.floor(token_to_give * 1e6)), 0);
-            buyData.writeBigUInt64LE(BigInt(Math.floor(lamports_per_sol * amount_used_in_sol)), 8);
-            const discriminator = Buffer.from(commcoinIdl.instructions.find((i: any) => i.name === 'buy')!.discriminator);
-            const finalBuyData = Buffer.concat([discriminator, buyData]);
-
-            const buyIx = new TransactionInstruction({
-                programId: PROGRAM_ID,
-                keys: [
-                    { pubkey: publicKey, isSigner: true, isWritable: true },
-                    { pubkey: mint, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurve, isSigner: false, isWritable: true },
-                    { pubkey: bondingCurveTokenVault, isSigner: false, isWritable: true },
-                    { pubkey: userAta, isSigner: false, isWritable: true },
-                    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-                    { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
-                    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
-                ],
-                data: finalBuyData,
-            });
+            const computeIx = ComputeBudgetProgram.setComputeUnitLimit({ units: CREATE_COIN_COMPUTE_UNITS });
 
-            const tx = new Transaction().add(createCoinIx, extendAccountIx, ataIx, buyIx);
+            const tx = new Transaction().add(computeIx, createCoinIx);
             tx.feePayer = publicKey;
             const { blockhash } = await connection.getLatestBlockhash();
             tx.recentBlockhash = blockhash;
@@ -14,34 +14,25 @@ export type Commcoin = {
   },
   "instructions": [
     {
-      "name": "allocAccount",
+      "name": "acceptAuthority",
       "discriminator": [
-        39,
-        100,
-        141,
-        203,
-        52,
-        184,
-        149,
-        166
+        107,
+        86,
+        198,
+        91,
+        33,
+        12,
+        107,
+        160
       ],
       "accounts": [
         {
-          "name": "user",
-          "writable": true,
+          "name": "newAuthority",
           "signer": true
         },
         {
-          "name": "curveAccount",
+          "name": "globalConfig",
           "writable": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
       ],
       "args": []
@@ -70,238 +61,48 @@ export type Commcoin = {
         },
         {
           "name": "bondingCurve",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  98,
-                  111,
-                  110,
-                  100,
-                  105,
-                  110,
-                  103,
-                  95,
-                  99,
-                  117,
-                  114,
-                  118,
-                  101
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ]
-          }
+          "writable": true
         },
         {
           "name": "bondingCurveTokenVault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "bondingCurve"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
+          "writable": true
         },
         {
           "name": "userCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "buyerRecord",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
+          "optional": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "referrer",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "systemProgram"
         },
         {
-          "name": "program",
-          "address": "6YuHH4kveCrEeEtVKM2nh18zU4XWFpEWqV8f5GbhdnzX"
+          "name": "program"
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "rent"
         }
       ],
       "args": [
@@ -312,475 +113,233 @@ export type Commcoin = {
         {
           "name": "maxSolCost",
           "type": "u64"
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
         }
       ]
     },
     {
-      "name": "configure",
+      "name": "buyExactSolIn",
       "discriminator": [
-        245,
-        7,
-        108,
-        117,
-        95,
-        196,
-        54,
-        217
+        56,
+        252,
+        116,
+        8,
+        158,
+        223,
+        205,
+        95
       ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "user",
           "writable": true,
           "signer": true
         },
         {
-          "name": "mintAuthority",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  98,
-                  111,
-                  110,
-                  100,
-                  105,
-                  110,
-                  103,
-                  95,
-                  99,
-                  117,
-                  114,
-                  118,
-                  101
-                ]
-              },
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  114,
-                  97,
-                  110,
-                  100,
-                  111,
-                  109,
-                  95,
-                  109,
-                  105,
-                  110,
-                  116,
-                  101,
-                  100
-                ]
-              }
-            ]
-          }
+          "name": "mint",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
+          "name": "bondingCurve",
+          "writable": true
+        },
         {
-          "name": "newConfig",
-          "type": {
-            "defined": {
-              "name": "config"
-            }
-          }
-        }
-      ]
-    },
-    {
-      "name": "createCoin",
-      "discriminator": [
-        208,
-        85,
-        34,
-        37,
-        235,
-        182,
-        111,
-        78
-      ],
-      "accounts": [
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
         {
-          "name": "signer",
-          "writable": true,
-          "signer": true
+          "name": "userCurveTokenVault",
+          "writable": true
         },
         {
-          "name": "mintAuthority",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  98,
-                  111,
-                  110,
-                  100,
-                  105,
-                  110,
-                  103,
-                  95,
-                  99,
-                  117,
-                  114,
-                  118,
-                  101
-                ]
-              },
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  114,
-                  97,
-                  110,
-                  100,
-                  111,
-                  109,
-                  95,
-                  109,
-                  105,
-                  110,
-                  116,
-                  101,
-                  100
-                ]
-              }
-            ]
-          }
+          "name": "globalConfig"
         },
         {
-          "name": "mint",
-          "writable": true,
-          "signer": true
+          "name": "feeRecipient",
+          "writable": true
         },
         {
           "name": "vault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  118,
-                  97,
-                  117,
-                  108,
-                  116,
-                  95,
-                  99,
-                  111,
-                  110,
-                  102,
-                  105,
-                  103,
-                  95,
-                  118,
-                  49
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ]
-          }
+          "writable": true
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "buyerRecord",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  118,
-                  97,
-                  117,
-                  108,
-                  116,
-                  95,
-                  116,
-                  111,
-                  107,
-                  101,
-                  110,
-                  115,
-                  95,
-                  118,
-                  49
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ]
-          }
+          "optional": true
         },
         {
-          "name": "bondingCurve",
+          "name": "referrer",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  98,
-                  111,
-                  110,
-                  100,
-                  105,
-                  110,
-                  103,
-                  95,
-                  99,
-                  117,
-                  114,
-                  118,
-                  101
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ]
-          }
+          "optional": true
         },
         {
-          "name": "bondingCurveTokenVault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "bondingCurve"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "program"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "solIn",
+          "type": "u64"
+        },
+        {
+          "name": "minTokensOut",
+          "type": "u64"
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
               ]
             }
           }
+        }
+      ]
+    },
+    {
+      "name": "castVote",
+      "discriminator": [
+        20,
+        212,
+        15,
+        189,
+        69,
+        180,
+        69,
+        151
+      ],
+      "accounts": [
+        {
+          "name": "voter",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "vault"
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "userStakeInfo"
         },
         {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+          "name": "proposal",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "voteRecord",
+          "writable": true
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "systemProgram"
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "support",
+          "type": "bool"
+        }
+      ]
     },
     {
-      "name": "extendAccount",
+      "name": "claimCreatorFees",
       "discriminator": [
+        0,
+        23,
+        125,
         234,
-        102,
-        194,
-        203,
-        150,
-        72,
-        62,
-        229
+        156,
+        118,
+        134,
+        89
       ],
       "accounts": [
         {
-          "name": "user",
+          "name": "creator",
           "writable": true,
           "signer": true
         },
         {
-          "name": "curveAccount",
-          "writable": true
+          "name": "mint"
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "bondingCurve",
+          "writable": true
         }
       ],
       "args": []
     },
     {
-      "name": "paintAccount",
+      "name": "claimMilestoneTokens",
       "discriminator": [
-        82,
-        79,
-        68,
-        9,
-        6,
-        73,
-        221,
-        86
+        44,
+        167,
+        51,
+        187,
+        212,
+        119,
+        212,
+        104
       ],
       "accounts": [
         {
-          "name": "user",
-          "writable": true,
+          "name": "creator",
           "signer": true
         },
         {
-          "name": "account",
+          "name": "mint"
+        },
+        {
+          "name": "vault"
+        },
+        {
+          "name": "milestones",
+          "writable": true
+        },
+        {
+          "name": "milestoneTokenAccount",
           "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "creatorTokenAccount",
+          "writable": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
         }
       ],
       "args": []
     },
     {
-      "name": "ropeAccount",
+      "name": "claimRewards",
       "discriminator": [
-        172,
-        209,
-        192,
-        236,
-        204,
-        145,
-        168,
-        198
+        4,
+        144,
+        132,
+        71,
+        116,
+        23,
+        151,
+        80
       ],
       "accounts": [
         {
@@ -789,427 +348,2870 @@ export type Commcoin = {
           "signer": true
         },
         {
-          "name": "baccount",
+          "name": "mint"
+        },
+        {
+          "name": "vault",
           "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "userStakeInfo",
+          "writable": true
         }
       ],
       "args": []
     },
     {
-      "name": "sell",
+      "name": "claimVested",
       "discriminator": [
-        51,
-        230,
-        133,
-        164,
-        1,
-        127,
-        131,
-        173
+        208,
+        190,
+        166,
+        114,
+        203,
+        225,
+        140,
+        208
       ],
       "accounts": [
         {
-          "name": "user",
-          "writable": true,
+          "name": "creator",
           "signer": true
         },
         {
-          "name": "mint",
+          "name": "mint"
+        },
+        {
+          "name": "creatorVesting",
           "writable": true
         },
         {
-          "name": "bondingCurve",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  109,
-                  109,
-                  99,
-                  111,
-                  105,
-                  110,
-                  95,
-                  98,
-                  111,
-                  110,
-                  100,
-                  105,
-                  110,
-                  103,
-                  95,
-                  99,
-                  117,
-                  114,
-                  118,
-                  101
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ]
-          }
+          "name": "vestingTokenAccount",
+          "writable": true
         },
         {
-          "name": "bondingCurveTokenVault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "bondingCurve"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
+          "name": "creatorTokenAccount",
+          "writable": true
         },
         {
-          "name": "userCurveTokenVault",
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "configure",
+      "discriminator": [
+        245,
+        7,
+        108,
+        117,
+        95,
+        196,
+        54,
+        217
+      ],
+      "accounts": [
+        {
+          "name": "admin",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
+          "signer": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "globalConfig",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "program"
         },
         {
-          "name": "program",
-          "address": "6YuHH4kveCrEeEtVKM2nh18zU4XWFpEWqV8f5GbhdnzX"
+          "name": "programData"
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "systemProgram"
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
-        },
-        {
-          "name": "maxSolCost",
-          "type": "u64"
+          "name": "newConfig",
+          "type": {
+            "defined": {
+              "name": "config"
+            }
+          }
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "bondingCurveState",
+      "name": "createCoin",
       "discriminator": [
+        208,
+        85,
+        34,
+        37,
+        235,
         182,
-        185,
-        75,
-        193,
-        72,
-        40,
-        132,
-        153
+        111,
+        78
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "mintAuthority"
+        },
+        {
+          "name": "mint",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "metadata",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "bondingCurve",
+          "writable": true
+        },
+        {
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "creatorVesting",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "vestingTokenAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "creatorTokenAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "creatorBuyerRecord",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "tokenMetadataProgram"
+        },
+        {
+          "name": "associatedTokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": {
+              "name": "createCoinArgs"
+            }
+          }
+        }
       ]
     },
     {
-      "name": "config",
+      "name": "createCoin2022",
       "discriminator": [
-        155,
-        12,
-        170,
-        224,
-        30,
-        250,
-        204,
-        130
+        96,
+        164,
+        131,
+        139,
+        19,
+        4,
+        0,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "mintAuthority"
+        },
+        {
+          "name": "mint",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "bondingCurve",
+          "writable": true
+        },
+        {
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "creatorVesting",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "vestingTokenAccount",
+          "docs": [
+            "the associated token program rejects any other address"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "creatorTokenAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "creatorBuyerRecord",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": {
+              "name": "createCoinArgs"
+            }
+          }
+        },
+        {
+          "name": "transferFee",
+          "type": {
+            "option": {
+              "defined": {
+                "name": "transferFeeArgs"
+              }
+            }
+          }
+        }
       ]
     },
     {
-      "name": "vaultConfig",
+      "name": "createProposal",
       "discriminator": [
-        99,
-        86,
-        43,
+        132,
+        116,
+        68,
+        174,
         216,
-        184,
-        102,
-        119,
-        77
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "vaultGoalReached",
-      "discriminator": [
-        232,
-        250,
-        139,
-        252,
-        185,
-        135,
-        245,
-        94
+        160,
+        198,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "proposer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "userStakeInfo"
+        },
+        {
+          "name": "proposal",
+          "writable": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "title",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        },
+        {
+          "name": "votingPeriod",
+          "type": "i64"
+        }
       ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "stakeAmountMustBePositive",
-      "msg": "Stake amount must be greater than zero."
     },
     {
-      "code": 6001,
-      "name": "refundAmountMustBePositive",
-      "msg": "Refund amount must be greater than zero."
+      "name": "expireLock",
+      "discriminator": [
+        63,
+        152,
+        206,
+        123,
+        101,
+        134,
+        1,
+        97
+      ],
+      "accounts": [
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "userStakeInfo",
+          "writable": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6002,
-      "name": "goalNotReached",
-      "msg": "The vault goal has not been reached yet."
-    },
+      "name": "finalizeProposal",
+      "discriminator": [
+        23,
+        68,
+        51,
+        167,
+        109,
+        173,
+        187,
+        164
+      ],
+      "accounts": [
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "vault"
+        },
+        {
+          "name": "proposal",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateAccount",
+      "discriminator": [
+        177,
+        228,
+        60,
+        125,
+        13,
+        116,
+        44,
+        84
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "program"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "target",
+          "writable": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "nominateAuthority",
+      "discriminator": [
+        148,
+        182,
+        144,
+        91,
+        186,
+        12,
+        118,
+        18
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "globalConfig",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "observePrice",
+      "discriminator": [
+        147,
+        178,
+        4,
+        21,
+        14,
+        213,
+        81,
+        159
+      ],
+      "accounts": [
+        {
+          "name": "mint"
+        },
+        {
+          "name": "bondingCurve"
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "priceObservation"
+        }
+      }
+    },
+    {
+      "name": "reachMilestones",
+      "discriminator": [
+        57,
+        131,
+        161,
+        59,
+        39,
+        71,
+        173,
+        144
+      ],
+      "accounts": [
+        {
+          "name": "vault"
+        },
+        {
+          "name": "milestones",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sell",
+      "discriminator": [
+        51,
+        230,
+        133,
+        164,
+        1,
+        127,
+        131,
+        173
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint",
+          "writable": true
+        },
+        {
+          "name": "bondingCurve",
+          "writable": true
+        },
+        {
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "userCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "referrer",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "program"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "minSolOutput",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "sellExactSolOut",
+      "discriminator": [
+        225,
+        180,
+        236,
+        35,
+        253,
+        152,
+        85,
+        124
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint",
+          "writable": true
+        },
+        {
+          "name": "bondingCurve",
+          "writable": true
+        },
+        {
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "userCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "referrer",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        },
+        {
+          "name": "program"
+        },
+        {
+          "name": "rent"
+        }
+      ],
+      "args": [
+        {
+          "name": "solOut",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokensIn",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setMilestones",
+      "discriminator": [
+        200,
+        178,
+        249,
+        11,
+        70,
+        59,
+        88,
+        148
+      ],
+      "accounts": [
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "vault"
+        },
+        {
+          "name": "milestones",
+          "writable": true
+        },
+        {
+          "name": "milestoneTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "creatorTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "milestones",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "milestoneArgs"
+              }
+            }
+          }
+        },
+        {
+          "name": "deadline",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "stake",
+      "discriminator": [
+        206,
+        176,
+        202,
+        18,
+        200,
+        209,
+        179,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "userStakeInfo",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "milestones",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "lockup",
+          "type": {
+            "defined": {
+              "name": "lockupTier"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "unstake",
+      "discriminator": [
+        90,
+        95,
+        107,
+        42,
+        205,
+        124,
+        50,
+        225
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "vault",
+          "writable": true
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "userStakeInfo",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateConfig",
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "globalConfig",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newConfig",
+          "type": {
+            "defined": {
+              "name": "config"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateMetadata",
+      "discriminator": [
+        170,
+        182,
+        43,
+        239,
+        97,
+        78,
+        225,
+        186
+      ],
+      "accounts": [
+        {
+          "name": "creator",
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "bondingCurve"
+        },
+        {
+          "name": "mintAuthority"
+        },
+        {
+          "name": "metadata",
+          "writable": true
+        },
+        {
+          "name": "tokenMetadataProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "updateMetadata2022",
+      "discriminator": [
+        206,
+        126,
+        252,
+        194,
+        129,
+        41,
+        141,
+        20
+      ],
+      "accounts": [
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint",
+          "writable": true
+        },
+        {
+          "name": "bondingCurve"
+        },
+        {
+          "name": "mintAuthority"
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "withdrawForMigration",
+      "discriminator": [
+        229,
+        30,
+        82,
+        104,
+        62,
+        251,
+        75,
+        69
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "globalConfig"
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "bondingCurve",
+          "writable": true
+        },
+        {
+          "name": "bondingCurveTokenVault",
+          "writable": true
+        },
+        {
+          "name": "migrationWallet",
+          "writable": true
+        },
+        {
+          "name": "migrationTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram"
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "bondingCurveState",
+      "discriminator": [
+        182,
+        185,
+        75,
+        193,
+        72,
+        40,
+        132,
+        153
+      ]
+    },
+    {
+      "name": "buyerRecord",
+      "discriminator": [
+        107,
+        122,
+        54,
+        31,
+        4,
+        54,
+        209,
+        38
+      ]
+    },
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "creatorVesting",
+      "discriminator": [
+        67,
+        134,
+        33,
+        113,
+        241,
+        68,
+        252,
+        138
+      ]
+    },
+    {
+      "name": "proposal",
+      "discriminator": [
+        26,
+        94,
+        189,
+        187,
+        116,
+        136,
+        53,
+        33
+      ]
+    },
+    {
+      "name": "userStakeInfo",
+      "discriminator": [
+        219,
+        233,
+        236,
+        123,
+        28,
+        113,
+        89,
+        56
+      ]
+    },
+    {
+      "name": "vaultConfig",
+      "discriminator": [
+        99,
+        86,
+        43,
+        216,
+        184,
+        102,
+        119,
+        77
+      ]
+    },
+    {
+      "name": "vaultMilestones",
+      "discriminator": [
+        0,
+        120,
+        250,
+        198,
+        178,
+        182,
+        138,
+        170
+      ]
+    },
+    {
+      "name": "voteRecord",
+      "discriminator": [
+        112,
+        9,
+        123,
+        165,
+        234,
+        9,
+        157,
+        167
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "coinCreated",
+      "discriminator": [
+        155,
+        248,
+        204,
+        17,
+        205,
+        237,
+        158,
+        104
+      ]
+    },
+    {
+      "name": "creatorFeesClaimed",
+      "discriminator": [
+        189,
+        178,
+        21,
+        181,
+        171,
+        179,
+        131,
+        1
+      ]
+    },
+    {
+      "name": "curveCompleted",
+      "discriminator": [
+        1,
+        174,
+        164,
+        127,
+        219,
+        129,
+        243,
+        14
+      ]
+    },
+    {
+      "name": "lockExpired",
+      "discriminator": [
+        73,
+        21,
+        197,
+        133,
+        4,
+        103,
+        125,
+        91
+      ]
+    },
+    {
+      "name": "metadataUpdated",
+      "discriminator": [
+        132,
+        36,
+        215,
+        246,
+        166,
+        90,
+        189,
+        44
+      ]
+    },
+    {
+      "name": "milestoneReached",
+      "discriminator": [
+        184,
+        36,
+        151,
+        97,
+        165,
+        74,
+        252,
+        130
+      ]
+    },
+    {
+      "name": "milestoneTokensClaimed",
+      "discriminator": [
+        215,
+        4,
+        221,
+        164,
+        209,
+        225,
+        204,
+        133
+      ]
+    },
+    {
+      "name": "proposalCreated",
+      "discriminator": [
+        186,
+        8,
+        160,
+        108,
+        81,
+        13,
+        51,
+        206
+      ]
+    },
+    {
+      "name": "proposalFinalized",
+      "discriminator": [
+        159,
+        104,
+        210,
+        220,
+        86,
+        209,
+        61,
+        51
+      ]
+    },
+    {
+      "name": "rewardsClaimed",
+      "discriminator": [
+        75,
+        98,
+        88,
+        18,
+        219,
+        112,
+        88,
+        121
+      ]
+    },
+    {
+      "name": "staked",
+      "discriminator": [
+        11,
+        146,
+        45,
+        205,
+        230,
+        58,
+        213,
+        240
+      ]
+    },
+    {
+      "name": "trade",
+      "discriminator": [
+        24,
+        254,
+        218,
+        152,
+        253,
+        43,
+        18,
+        81
+      ]
+    },
+    {
+      "name": "unstaked",
+      "discriminator": [
+        27,
+        179,
+        156,
+        215,
+        47,
+        71,
+        195,
+        7
+      ]
+    },
+    {
+      "name": "vaultGoalReached",
+      "discriminator": [
+        232,
+        250,
+        139,
+        252,
+        185,
+        135,
+        245,
+        94
+      ]
+    },
+    {
+      "name": "vestedTokensClaimed",
+      "discriminator": [
+        15,
+        7,
+        248,
+        165,
+        41,
+        118,
+        87,
+        175
+      ]
+    },
+    {
+      "name": "voteCast",
+      "discriminator": [
+        39,
+        53,
+        195,
+        104,
+        188,
+        17,
+        225,
+        213
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "stakeAmountMustBePositive",
+      "msg": "Stake amount must be greater than zero."
+    },
+    {
+      "code": 6001,
+      "name": "refundAmountMustBePositive",
+      "msg": "Refund amount must be greater than zero."
+    },
+    {
+      "code": 6002,
+      "name": "goalNotReached",
+      "msg": "The vault goal has not been reached yet."
+    },
+    {
+      "code": 6003,
+      "name": "insufficientStakeForRefund",
+      "msg": "User does not have enough staked tokens for this refund amount."
+    },
+    {
+      "code": 6004,
+      "name": "invalidVaultTokenAccount",
+      "msg": "The vault token account provided is invalid."
+    },
+    {
+      "code": 6005,
+      "name": "mintMismatch",
+      "msg": "Token mint mismatch."
+    },
+    {
+      "code": 6006,
+      "name": "stakeInfoMismatch",
+      "msg": "User stake info does not match the staker or vault."
+    },
+    {
+      "code": 6007,
+      "name": "goalCannotBeZero",
+      "msg": "Vault goal cannot be zero."
+    },
+    {
+      "code": 6008,
+      "name": "invalidVaultName",
+      "msg": "Vault name is invalid (empty or too long)."
+    },
+    {
+      "code": 6009,
+      "name": "noStakers",
+      "msg": "Nobody is staked in this vault."
+    },
+    {
+      "code": 6010,
+      "name": "noRewardsToClaim",
+      "msg": "There are no rewards to claim."
+    },
+    {
+      "code": 6011,
+      "name": "stakeLocked",
+      "msg": "Stake is still locked."
+    },
+    {
+      "code": 6012,
+      "name": "lockupDowngrade",
+      "msg": "An active lockup can't be shortened."
+    },
+    {
+      "code": 6013,
+      "name": "invalidMilestones",
+      "msg": "Milestones must be increasing, ahead of the current stake and use perks 0 to 31."
+    },
+    {
+      "code": 6014,
+      "name": "nothingToClaim",
+      "msg": "No unlocked milestone tokens to claim."
+    },
+    {
+      "code": 6015,
+      "name": "invalidProposal",
+      "msg": "Proposal title, uri or voting period is invalid."
+    },
+    {
+      "code": 6016,
+      "name": "insufficientProposerWeight",
+      "msg": "Not enough stake weight to create a proposal."
+    },
+    {
+      "code": 6017,
+      "name": "votingClosed",
+      "msg": "Voting on this proposal is closed."
+    },
+    {
+      "code": 6018,
+      "name": "votingNotEnded",
+      "msg": "Voting on this proposal hasn't ended yet."
+    },
+    {
+      "code": 6019,
+      "name": "noVotingPower",
+      "msg": "No stake weight to vote with at the proposal snapshot."
+    },
+    {
+      "code": 6020,
+      "name": "lockNotExpired",
+      "msg": "The stake is not locked or its lock hasn't run out yet."
+    }
+  ],
+  "types": [
+    {
+      "name": "allowList",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "endsAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "antiSnipeConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "protectedSlots",
+            "type": "u64"
+          },
+          {
+            "name": "maxWalletBps",
+            "type": "u16"
+          },
+          {
+            "name": "cooldownSlots",
+            "type": "u64"
+          },
+          {
+            "name": "maxProtectedSlots",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "antiSnipeParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "protectedSlots",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokensPerWallet",
+            "type": "u64"
+          },
+          {
+            "name": "cooldownSlots",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bondingCurveState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "virtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "realSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "realTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "tokenTotalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "complete",
+            "type": "bool"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "creatorFeesAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFeesClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": {
+              "defined": {
+                "name": "curveType"
+              }
+            }
+          },
+          {
+            "name": "curveParams",
+            "type": {
+              "defined": {
+                "name": "curveParams"
+              }
+            }
+          },
+          {
+            "name": "initialRealTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "launchSlot",
+            "type": "u64"
+          },
+          {
+            "name": "antiSnipe",
+            "type": {
+              "defined": {
+                "name": "antiSnipeParams"
+              }
+            }
+          },
+          {
+            "name": "tradingStartsAt",
+            "type": "i64"
+          },
+          {
+            "name": "allowListRoot",
+            "docs": [
+              "Merkle root of the wallets allowed to buy before `allow_list_ends_at`, all zero when unused.",
+              "",
+              "Proof format, as published by the server:",
+              "- leaf = sha256(0x00 || wallet pubkey bytes)",
+              "- node = sha256(0x01 || a || b), where a <= b bytewise (pairs are sorted, no left/right flags)",
+              "- the proof is the list of sibling hashes from the leaf up to the root,",
+              "passed to `buy` as `Vec<[u8; 32]>`. Odd nodes are promoted to the next level unhashed."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "allowListEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "priceCumulative",
+            "docs": [
+              "Running sum of the spot price (lamports per PRICE_SCALE base units) times the slots it was quoted for.",
+              "The TWAP between two observations is the difference of the sums divided by the slots between them,",
+              "see `observe_price` for an up-to-date reading."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "priceLastSlot",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "bondingCurve",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokensBought",
+            "type": "u64"
+          },
+          {
+            "name": "lastBuySlot",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "coinCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "bondingCurve",
+            "type": "pubkey"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenTotalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "decimals",
+            "type": "u8"
+          },
+          {
+            "name": "virtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "realTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": {
+              "defined": {
+                "name": "curveType"
+              }
+            }
+          },
+          {
+            "name": "creatorAllocation",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "config",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeRecipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "curveLimit",
+            "type": "u64"
+          },
+          {
+            "name": "initialVirtualTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "initialVirtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "initialRealTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "totalTokenSupply",
+            "type": "u64"
+          },
+          {
+            "name": "buyFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "sellFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "migrationFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "stakerFeeShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "referralFeeShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "creatorFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxCreatorAllocationBps",
+            "type": "u16"
+          },
+          {
+            "name": "allowedCurveTypes",
+            "type": "u8"
+          },
+          {
+            "name": "linearParams",
+            "type": {
+              "defined": {
+                "name": "curveParams"
+              }
+            }
+          },
+          {
+            "name": "exponentialParams",
+            "type": {
+              "defined": {
+                "name": "curveParams"
+              }
+            }
+          },
+          {
+            "name": "launchBounds",
+            "type": {
+              "defined": {
+                "name": "launchBounds"
+              }
+            }
+          },
+          {
+            "name": "antiSnipe",
+            "type": {
+              "defined": {
+                "name": "antiSnipeConfig"
+              }
+            }
+          },
+          {
+            "name": "governance",
+            "type": {
+              "defined": {
+                "name": "governanceConfig"
+              }
+            }
+          },
+          {
+            "name": "minVestingCliff",
+            "type": "i64"
+          },
+          {
+            "name": "minVestingDuration",
+            "type": "i64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                6
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "createCoinArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "vaultName",
+            "type": "string"
+          },
+          {
+            "name": "vaultGoal",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": {
+              "defined": {
+                "name": "curveType"
+              }
+            }
+          },
+          {
+            "name": "launchParams",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "launchParams"
+                }
+              }
+            }
+          },
+          {
+            "name": "initialBuySol",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "antiSnipe",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "antiSnipeParams"
+                }
+              }
+            }
+          },
+          {
+            "name": "tradingStartsAt",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "allowList",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "allowList"
+                }
+              }
+            }
+          },
+          {
+            "name": "creatorVesting",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "vestingArgs"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "creatorFeesClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "creatorVesting",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalAmount",
+            "type": "u64"
+          },
+          {
+            "name": "claimedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "startAt",
+            "type": "i64"
+          },
+          {
+            "name": "cliffAt",
+            "type": "i64"
+          },
+          {
+            "name": "endAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "curveCompleted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "realSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "realTokenReserves",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "curveParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startPrice",
+            "type": "u64"
+          },
+          {
+            "name": "endPrice",
+            "type": "u64"
+          },
+          {
+            "name": "growthBps",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "curveType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "constantProduct"
+          },
+          {
+            "name": "linear"
+          },
+          {
+            "name": "exponential"
+          }
+        ]
+      }
+    },
+    {
+      "name": "governanceConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVotingPeriod",
+            "type": "i64"
+          },
+          {
+            "name": "maxVotingPeriod",
+            "type": "i64"
+          },
+          {
+            "name": "quorumBps",
+            "type": "u16"
+          },
+          {
+            "name": "minProposerWeightBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "launchBounds",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minTokenSupply",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokenSupply",
+            "type": "u64"
+          },
+          {
+            "name": "maxDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "minVirtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "maxVirtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "minVirtualTokenBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxVirtualTokenBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "launchParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenTotalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "decimals",
+            "type": "u8"
+          },
+          {
+            "name": "virtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserves",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lockExpired",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "staker",
+            "type": "pubkey"
+          },
+          {
+            "name": "weightedStake",
+            "type": "u64"
+          },
+          {
+            "name": "vaultTotalWeight",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lockupTier",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "flexible"
+          },
+          {
+            "name": "days7"
+          },
+          {
+            "name": "days30"
+          },
+          {
+            "name": "days90"
+          }
+        ]
+      }
+    },
+    {
+      "name": "metadataUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "milestone",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "threshold",
+            "type": "u64"
+          },
+          {
+            "name": "releaseAmount",
+            "type": "u64"
+          },
+          {
+            "name": "perk",
+            "type": "u8"
+          },
+          {
+            "name": "reached",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "milestoneArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "thresholdBps",
+            "type": "u16"
+          },
+          {
+            "name": "releaseAmount",
+            "type": "u64"
+          },
+          {
+            "name": "perk",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "milestoneReached",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "index",
+            "type": "u8"
+          },
+          {
+            "name": "threshold",
+            "type": "u64"
+          },
+          {
+            "name": "lockedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "releaseAmount",
+            "type": "u64"
+          },
+          {
+            "name": "perk",
+            "type": "u8"
+          },
+          {
+            "name": "perks",
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "milestoneTokensClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "priceObservation",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "priceCumulative",
+            "type": "u128"
+          }
+        ]
+      }
+    },
     {
-      "code": 6003,
-      "name": "insufficientStakeForRefund",
-      "msg": "User does not have enough staked tokens for this refund amount."
+      "name": "proposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "proposer",
+            "type": "pubkey"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "snapshotSlot",
+            "type": "u64"
+          },
+          {
+            "name": "snapshotWeight",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "yesWeight",
+            "type": "u64"
+          },
+          {
+            "name": "noWeight",
+            "type": "u64"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "proposalStatus"
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "invalidVaultTokenAccount",
-      "msg": "The vault token account provided is invalid."
+      "name": "proposalCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "proposal",
+            "type": "pubkey"
+          },
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "proposer",
+            "type": "pubkey"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "snapshotSlot",
+            "type": "u64"
+          },
+          {
+            "name": "snapshotWeight",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "mintMismatch",
-      "msg": "Token mint mismatch."
+      "name": "proposalFinalized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "proposal",
+            "type": "pubkey"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "proposalStatus"
+              }
+            }
+          },
+          {
+            "name": "yesWeight",
+            "type": "u64"
+          },
+          {
+            "name": "noWeight",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "stakeInfoMismatch",
-      "msg": "User stake info does not match the staker or vault."
+      "name": "proposalStatus",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "active"
+          },
+          {
+            "name": "passed"
+          },
+          {
+            "name": "rejected"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "goalCannotBeZero",
-      "msg": "Vault goal cannot be zero."
+      "name": "rewardsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "staker",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardsClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "staked",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "staker",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "userTotalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "vaultTotalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": {
+                "name": "lockupTier"
+              }
+            }
+          },
+          {
+            "name": "unlockAt",
+            "type": "i64"
+          },
+          {
+            "name": "vaultTotalWeight",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "trade",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "isBuy",
+            "type": "bool"
+          },
+          {
+            "name": "solAmount",
+            "type": "u64"
+          },
+          {
+            "name": "tokenAmount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "stakerFee",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFee",
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "referrerFee",
+            "type": "u64"
+          },
+          {
+            "name": "virtualSolReserves",
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserves",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "transferFeeArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "basisPoints",
+            "type": "u16"
+          },
+          {
+            "name": "maximumFee",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "invalidVaultName",
-      "msg": "Vault name is invalid (empty or too long)."
-    }
-  ],
-  "types": [
-    {
-      "name": "bondingCurveState",
+      "name": "unstaked",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "virtualSolReserves",
-            "type": "u64"
+            "name": "vault",
+            "type": "pubkey"
           },
           {
-            "name": "virtualTokenReserves",
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "staker",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "realSolReserves",
+            "name": "userTotalStaked",
             "type": "u64"
           },
           {
-            "name": "realTokenReserves",
+            "name": "vaultTotalStaked",
             "type": "u64"
           },
           {
-            "name": "tokenTotalSupply",
+            "name": "vaultTotalWeight",
             "type": "u64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "timestamp",
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "config",
+      "name": "userStakeInfo",
       "type": {
         "kind": "struct",
-        "fields": []
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "staker",
+            "type": "pubkey"
+          },
+          {
+            "name": "vaultConfig",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountStaked",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "rewardDebt",
+            "type": "u128"
+          },
+          {
+            "name": "pendingRewards",
+            "type": "u64"
+          },
+          {
+            "name": "rewardsClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": {
+                "name": "lockupTier"
+              }
+            }
+          },
+          {
+            "name": "unlockAt",
+            "type": "i64"
+          },
+          {
+            "name": "weightedStake",
+            "type": "u64"
+          },
+          {
+            "name": "stakeChangedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "lockedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "previousWeightedStake",
+            "type": "u64"
+          },
+          {
+            "name": "previousChangedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
       }
     },
     {
@@ -1217,6 +3219,10 @@ export type Commcoin = {
       "type": {
         "kind": "struct",
         "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
           {
             "name": "name",
             "type": "string"
@@ -1252,6 +3258,39 @@ export type Commcoin = {
           {
             "name": "bumpTokenVault",
             "type": "u8"
+          },
+          {
+            "name": "accRewardPerShare",
+            "type": "u128"
+          },
+          {
+            "name": "totalRewards",
+            "type": "u64"
+          },
+          {
+            "name": "totalWeight",
+            "type": "u64"
+          },
+          {
+            "name": "proposalCount",
+            "type": "u64"
+          },
+          {
+            "name": "undistributedRewards",
+            "type": "u64"
+          },
+          {
+            "name": "lockedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
           }
         ]
       }
@@ -1271,6 +3310,253 @@ export type Commcoin = {
           }
         ]
       }
+    },
+    {
+      "name": "vaultMilestones",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "count",
+            "type": "u8"
+          },
+          {
+            "name": "milestones",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "milestone"
+                  }
+                },
+                5
+              ]
+            }
+          },
+          {
+            "name": "perks",
+            "type": "u32"
+          },
+          {
+            "name": "lockedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "unlockedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "claimedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "deadline",
+            "type": "i64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "vestedTokensClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "vestingArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allocationBps",
+            "type": "u16"
+          },
+          {
+            "name": "startAt",
+            "type": "i64"
+          },
+          {
+            "name": "cliffAt",
+            "type": "i64"
+          },
+          {
+            "name": "endAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "voteCast",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "proposal",
+            "type": "pubkey"
+          },
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          },
+          {
+            "name": "yesWeight",
+            "type": "u64"
+          },
+          {
+            "name": "noWeight",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "voteRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proposal",
+            "type": "pubkey"
+          },
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "constants": [
+    {
+      "name": "bondingCurveSeed",
+      "type": "bytes",
+      "value": "[99, 111, 109, 109, 99, 111, 105, 110, 95, 98, 111, 110, 100, 105, 110, 103, 95, 99, 117, 114, 118, 101]"
+    },
+    {
+      "name": "buyerRecordSeed",
+      "type": "bytes",
+      "value": "[98, 117, 121, 101, 114, 95, 114, 101, 99, 111, 114, 100, 95, 118, 49]"
+    },
+    {
+      "name": "configSeed",
+      "type": "bytes",
+      "value": "[99, 111, 110, 102, 105, 103, 95, 118, 49]"
+    },
+    {
+      "name": "creatorVestingSeed",
+      "type": "bytes",
+      "value": "[99, 114, 101, 97, 116, 111, 114, 95, 118, 101, 115, 116, 105, 110, 103, 95, 118, 49]"
+    },
+    {
+      "name": "mintAuthoritySeed",
+      "type": "bytes",
+      "value": "[109, 105, 110, 116, 95, 97, 117, 116, 104, 111, 114, 105, 116, 121, 95, 118, 49]"
+    },
+    {
+      "name": "proposalSeed",
+      "type": "bytes",
+      "value": "[112, 114, 111, 112, 111, 115, 97, 108, 95, 118, 49]"
+    },
+    {
+      "name": "userStakeInfoSeed",
+      "type": "bytes",
+      "value": "[117, 115, 101, 114, 95, 115, 116, 97, 107, 101, 95, 118, 49]"
+    },
+    {
+      "name": "vaultConfigSeed",
+      "type": "bytes",
+      "value": "[118, 97, 117, 108, 116, 95, 99, 111, 110, 102, 105, 103, 95, 118, 49]"
+    },
+    {
+      "name": "vaultMilestonesSeed",
+      "type": "bytes",
+      "value": "[118, 97, 117, 108, 116, 95, 109, 105, 108, 101, 115, 116, 111, 110, 101, 115, 95, 118, 49]"
+    },
+    {
+      "name": "vaultTokenSeed",
+      "type": "bytes",
+      "value": "[118, 97, 117, 108, 116, 95, 116, 111, 107, 101, 110, 115, 95, 118, 49]"
+    },
+    {
+      "name": "voteRecordSeed",
+      "type": "bytes",
+      "value": "[118, 111, 116, 101, 95, 114, 101, 99, 111, 114, 100, 95, 118, 49]"
     }
   ]
 };
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum CommcoinError {
+    #[msg("Trade amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Trade price exceeds the slippage limit.")]
+    SlippageExceeded,
+    #[msg("Bonding curve does not hold enough reserves for this trade.")]
+    InsufficientReserves,
+    #[msg("Arithmetic overflow in curve math.")]
+    MathOverflow,
+    #[msg("Signer is not allowed to perform this action.")]
+    Unauthorized,
+    #[msg("Config values are out of range.")]
+    InvalidConfig,
+    #[msg("Fee recipient does not match the config.")]
+    InvalidFeeRecipient,
+    #[msg("Bonding curve is complete, trading has stopped.")]
+    CurveComplete,
+    #[msg("Bonding curve has not completed yet.")]
+    CurveNotComplete,
+    #[msg("Token name, symbol or uri is invalid (empty or too long).")]
+    InvalidMetadata,
+    #[msg("Transfer fee is above the allowed maximum.")]
+    InvalidTransferFee,
+    #[msg("Curve type is not enabled in the config.")]
+    CurveTypeNotAllowed,
+    #[msg("Launch parameters are outside the config bounds.")]
+    InvalidLaunchParams,
+    #[msg("Anti-sniping window settings are invalid.")]
+    InvalidLaunchWindow,
+    #[msg("Buyer record is required during the protected launch window.")]
+    BuyerRecordRequired,
+    #[msg("Buy exceeds the per-wallet cap of the launch window.")]
+    WalletCapExceeded,
+    #[msg("Wallet is still in its buy cooldown.")]
+    BuyCooldownActive,
+    #[msg("Trading has not started yet.")]
+    TradingNotStarted,
+    #[msg("Wallet is not on the allow-list of this launch.")]
+    NotOnAllowList,
+    #[msg("Launch schedule is invalid.")]
+    InvalidLaunchSchedule,
+    #[msg("Account is not a versioned account of this program.")]
+    InvalidMigrationTarget,
+    #[msg("Account is already on the current layout version.")]
+    AccountUpToDate,
+    #[msg("No upgrade path from this layout version.")]
+    UnsupportedVersion,
+    #[msg("Reserve invariant violated.")]
+    InvariantViolated,
+    #[msg("Payout would leave the account below its rent-exempt minimum.")]
+    RentExemptionViolated,
+    #[msg("Traders can't refer themselves.")]
+    InvalidReferrer,
+    #[msg("Creator vesting allocation or schedule is invalid.")]
+    InvalidVestingSchedule,
+    #[msg("Creator vesting accounts must be passed exactly when a vesting schedule is.")]
+    VestingAccountsMismatch,
+    #[msg("No vested tokens to claim.")]
+    NothingVested,
+    #[msg("An initial buy needs the creator's token account.")]
+    CreatorTokenAccountRequired,
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{CurveType, LockupTier, ProposalStatus};
+
+#[event]
+pub struct CoinCreated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub vault: Pubkey,
+    pub token_total_supply: u64,
+    pub decimals: u8,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub curve_type: CurveType,
+    pub creator_allocation: u64, // minted into the creator vesting account, 0 without one
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Trade {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64,   // lamports in/out of the curve, excluding fees
+    pub token_amount: u64,
+    pub fee: u64,          // total trading fee, including staker_fee and referrer_fee
+    pub staker_fee: u64,   // share of the fee paid to the coin's vault stakers
+    pub creator_fee: u64,  // accrued to the coin creator, on top of `fee`
+    pub referrer: Option<Pubkey>, // wallet credited with the trade, if any
+    pub referrer_fee: u64, // share of the platform fee paid to the referrer
+    pub virtual_sol_reserves: u64,   // post-trade
+    pub virtual_token_reserves: u64, // post-trade
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveCompleted {
+    pub mint: Pubkey,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+#[event]
+pub struct Staked {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub user_total_staked: u64,  // post-stake
+    pub vault_total_staked: u64, // post-stake
+    pub lockup: LockupTier,      // post-stake, applies to the whole position
+    pub unlock_at: i64,
+    pub vault_total_weight: u64, // post-stake
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub user_total_staked: u64,  // post-unstake
+    pub vault_total_staked: u64, // post-unstake
+    pub vault_total_weight: u64, // post-unstake
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockExpired {
+    pub vault: Pubkey,
+    pub staker: Pubkey,
+    pub weighted_stake: u64,     // back at 1x
+    pub vault_total_weight: u64, // post-expiry
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneReached {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub threshold: u64,      // locked tokens of the milestone
+    pub locked_tokens: u64,  // vault locked tokens that reached it
+    pub release_amount: u64, // creator-locked tokens unlocked by it
+    pub perk: u8,            // VaultMilestones::NO_PERK for none
+    pub perks: u32,          // all perks unlocked so far
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneTokensClaimed {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub vault: Pubkey,
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub title: String,
+    pub uri: String,
+    pub snapshot_slot: u64,
+    pub snapshot_weight: u64, // vault stake weight the quorum is taken from
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub vault: Pubkey,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub yes_weight: u64, // post-vote
+    pub no_weight: u64,  // post-vote
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalFinalized {
+    pub vault: Pubkey,
+    pub proposal: Pubkey,
+    pub status: ProposalStatus,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,          // lamports paid out
+    pub rewards_claimed: u64, // lifetime total for this staker
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64, // lifetime total, out of the vesting allocation
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,         // lamports paid out
+    pub total_claimed: u64,  // lifetime total for this coin
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataUpdated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
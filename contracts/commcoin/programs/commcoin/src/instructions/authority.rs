@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CommcoinError, seeds::CONFIG_SEED, states::Config};
+
+#[derive(Accounts)]
+pub struct NominateAuthority<'info> {
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ CommcoinError::Unauthorized,
+    )]
+    global_config: Account<'info, Config>,
+}
+
+impl<'info> NominateAuthority<'info> {
+    //  step 1: current authority proposes a successor, Pubkey::default() cancels a nomination
+    pub fn process(&mut self, new_authority: Pubkey) -> Result<()> {
+        self.global_config.pending_authority = new_authority;
+
+        msg!("Authority nominated: {}", new_authority);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        constraint = global_config.pending_authority == new_authority.key() @ CommcoinError::Unauthorized,
+    )]
+    global_config: Account<'info, Config>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+    //  step 2: the nominee signs to take over
+    pub fn process(&mut self) -> Result<()> {
+        self.global_config.authority = self.new_authority.key();
+        self.global_config.pending_authority = Pubkey::default();
+
+        msg!("Authority accepted: {}", self.global_config.authority);
+        Ok(())
+    }
+}
@@ -1,21 +1,23 @@
 use anchor_lang::{prelude::*};
 
 use anchor_spl::{
-    associated_token::{Create as CreateIdempotent, AssociatedToken, get_associated_token_address, create_idempotent},
-    token::{mint_to, set_authority, transfer as token_transfer, MintTo, Mint, Token, TokenAccount, Transfer as TokenTransfer},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use anchor_lang::system_program::{self, transfer as sol_transfer, Transfer as SOLTransfer};
-
-declare_program!(commcoin);
-use commcoin::program::Commcoin;
+use anchor_lang::system_program::{transfer as sol_transfer, Transfer as SOLTransfer};
 
 use crate::{
-    states::{Config, BondingCurveState},
+    errors::CommcoinError,
+    events::{CurveCompleted, Trade},
+    math,
+    program::Commcoin,
+    seeds::{BONDING_CURVE_SEED, BUYER_RECORD_SEED, CONFIG_SEED, VAULT_CONFIG_SEED},
+    states::{BondingCurveState, BuyerRecord, Config, VaultConfig},
 };
 
 
 
+
 #[derive(Accounts)]
 pub struct Buy<'info> {
     // Accounts for creating the token & metadata
@@ -23,11 +25,11 @@ pub struct Buy<'info> {
     pub user: Signer<'info>, // User creating the token, pays rent
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"commcoin_bonding_curve", mint.key().as_ref()], // Seed with mint for uniqueness
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()], // Seed with mint for uniqueness
         bump
     )]
     pub bonding_curve: Account<'info, BondingCurveState>,
@@ -36,27 +38,110 @@ pub struct Buy<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub bonding_curve_token_vault: Account<'info, TokenAccount>,
+    pub bonding_curve_token_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, Config>,
+
+    /// CHECK: only receives lamports, address is checked against the config
+    #[account(
+        mut,
+        address = global_config.fee_recipient @ CommcoinError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    // Community vault of this coin, receives the stakers' share of the fee
+    #[account(
+        mut,
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    // Required while the coin is in its protected launch window, tracks the wallet's buys
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = BuyerRecord::LEN,
+        seeds = [BUYER_RECORD_SEED, bonding_curve.key().as_ref(), user.key().as_ref()],
+        bump
     )]
-    pub user_curve_token_vault: Account<'info, TokenAccount>,
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// CHECK: referral wallet, only receives its share of the platform fee
+    #[account(mut)]
+    pub referrer: Option<UncheckedAccount<'info>>,
 
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub program: Program<'info, Commcoin>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> Buy<'info>{
-    pub fn buy(&mut self, amount: u64, max_sol_cost: u64, bump_config: u8) -> Result<()> {
-        let token_transfer_context = TokenTransfer {
+    // Exact tokens out, the total cost including fees is capped by `max_sol_cost`
+    pub fn buy(
+        &mut self,
+        amount: u64,
+        max_sol_cost: u64,
+        proof: &[[u8; 32]],
+        bonding_curve_bump: u8,
+        buyer_record_bump: Option<u8>,
+    ) -> Result<()> {
+        require!(!self.bonding_curve.complete, CommcoinError::CurveComplete);
+        self.bonding_curve.check_trading_open(&self.user.key(), proof)?;
+
+        let quote = BuyQuote::new(&self.bonding_curve, &self.global_config, amount)?;
+        require!(quote.total_cost()? <= max_sol_cost, CommcoinError::SlippageExceeded);
+        self.execute(quote, bonding_curve_bump, buyer_record_bump)
+    }
+
+    // Exact SOL in, fees included. Buys as many tokens as `sol_in` pays for,
+    // any dust the curve can't turn into a whole base unit stays with the user.
+    pub fn buy_exact_sol_in(
+        &mut self,
+        sol_in: u64,
+        min_tokens_out: u64,
+        proof: &[[u8; 32]],
+        bonding_curve_bump: u8,
+        buyer_record_bump: Option<u8>,
+    ) -> Result<()> {
+        require!(!self.bonding_curve.complete, CommcoinError::CurveComplete);
+        self.bonding_curve.check_trading_open(&self.user.key(), proof)?;
+
+        // The launch window cap is checked on the result, like for `buy`
+        let quote = BuyQuote::for_sol_budget(&self.bonding_curve, &self.global_config, sol_in, u64::MAX)?;
+        require!(quote.token_amount >= min_tokens_out, CommcoinError::SlippageExceeded);
+        self.execute(quote, bonding_curve_bump, buyer_record_bump)
+    }
+
+    fn execute(&mut self, mut quote: BuyQuote, bonding_curve_bump: u8, buyer_record_bump: Option<u8>) -> Result<()> {
+        self.check_launch_window(quote.token_amount, buyer_record_bump)?;
+        let referrer = check_referrer(&self.referrer, &self.user.key())?;
+        let mut split = split_fee(quote.fee, &self.global_config, &self.vault, referrer.is_some())?;
+        let referrer_info = self.referrer.as_ref().map(|referrer| referrer.to_account_info());
+        let waived = split.skip_unpayable(&self.fee_recipient.to_account_info(), referrer_info.as_ref(), &Rent::get()?);
+        quote.fee = math::sub(quote.fee, waived)?;
+        let BuyQuote { token_amount: amount, sol_cost, fee, creator_fee } = quote;
+        let FeeSplit { platform_fee, staker_fee, referrer_fee } = split;
+
+        let token_transfer_context = TransferChecked {
             from: self.bonding_curve_token_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
             to: self.user_curve_token_vault.to_account_info(),
             authority: self.bonding_curve.to_account_info()
         };
@@ -64,17 +149,16 @@ impl<'info> Buy<'info>{
         let toke_cpi_program = self.token_program.to_account_info();
         let mint_pubkey = self.mint.key();
         let mint_key_bytes: &[u8] = mint_pubkey.as_ref();
-        let signer_seeds: &[&[&[u8]]] = &[&[b"commcoin_bonding_curve", mint_key_bytes, &[bump_config]]];
+        let signer_seeds: &[&[&[u8]]] = &[&[BONDING_CURVE_SEED, mint_key_bytes, &[bonding_curve_bump]]];
         let token_transfer_cpi_context = CpiContext::new_with_signer(
             toke_cpi_program,
             token_transfer_context,
             signer_seeds
         );
 
-        token_transfer(token_transfer_cpi_context, amount)?;
+        // With a Token-2022 transfer fee the user receives `amount` minus the withheld royalty
+        transfer_checked(token_transfer_cpi_context, amount, self.mint.decimals)?;
 
-        let curve_account = max_sol_cost.saturating_sub(max_sol_cost*0.1 as u64);
-        
         let sol_transfer_cpi_context = CpiContext::new(
             self.system_program.to_account_info(),
             SOLTransfer {
@@ -82,9 +166,355 @@ impl<'info> Buy<'info>{
                 to: self.bonding_curve.to_account_info(),     // Account receiving SOL
             }
         );
-        sol_transfer(sol_transfer_cpi_context, curve_account)?;
+        // The creator fee is held by the curve account until claimed
+        let curve_amount = math::add(sol_cost, creator_fee)?;
+        sol_transfer(sol_transfer_cpi_context, curve_amount)?;
+
+        if platform_fee > 0 {
+            let fee_transfer_cpi_context = CpiContext::new(
+                self.system_program.to_account_info(),
+                SOLTransfer {
+                    from: self.user.to_account_info(),
+                    to: self.fee_recipient.to_account_info(),
+                }
+            );
+            sol_transfer(fee_transfer_cpi_context, platform_fee)?;
+        }
+
+        if staker_fee > 0 {
+            let staker_fee_cpi_context = CpiContext::new(
+                self.system_program.to_account_info(),
+                SOLTransfer {
+                    from: self.user.to_account_info(),
+                    to: self.vault.to_account_info(),
+                }
+            );
+            sol_transfer(staker_fee_cpi_context, staker_fee)?;
+            self.vault.distribute_rewards(staker_fee)?;
+        }
+
+        if let Some(referrer_info) = &self.referrer {
+            if referrer_fee > 0 {
+                let referrer_fee_cpi_context = CpiContext::new(
+                    self.system_program.to_account_info(),
+                    SOLTransfer {
+                        from: self.user.to_account_info(),
+                        to: referrer_info.to_account_info(),
+                    }
+                );
+                sol_transfer(referrer_fee_cpi_context, referrer_fee)?;
+            }
+        }
+
+        self.bonding_curve.apply_buy(amount, sol_cost)?;
+        self.bonding_curve.accrue_creator_fee(creator_fee)?;
+        self.bonding_curve_token_vault.reload()?;
+        self.bonding_curve
+            .check_invariants(&self.bonding_curve.to_account_info(), self.bonding_curve_token_vault.amount)?;
+
+        quote.emit_trade(self.mint.key(), self.user.key(), &split, referrer, &self.bonding_curve)?;
+        complete_if_needed(&mut self.bonding_curve, &self.global_config, self.mint.key());
+
+        msg!("Instruction: Buy");
+        msg!("Tokens: {}, SOL cost: {}, fee: {}", amount, sol_cost, fee);
+
+        Ok(())
+    }
+
+    // Per-wallet cap and cooldown while the coin is in its protected launch window
+    fn check_launch_window(&mut self, amount: u64, buyer_record_bump: Option<u8>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        if !self.bonding_curve.in_protected_window(slot) {
+            return Ok(());
+        }
+
+        let anti_snipe = self.bonding_curve.anti_snipe;
+        let bonding_curve_key = self.bonding_curve.key();
+        let (Some(buyer_record), Some(bump)) = (self.buyer_record.as_mut(), buyer_record_bump) else {
+            return err!(CommcoinError::BuyerRecordRequired);
+        };
+
+        if anti_snipe.cooldown_slots > 0 && buyer_record.last_buy_slot > 0 {
+            require!(
+                slot >= buyer_record.last_buy_slot.saturating_add(anti_snipe.cooldown_slots),
+                CommcoinError::BuyCooldownActive
+            );
+        }
+
+        let tokens_bought = math::add(buyer_record.tokens_bought, amount)?;
+        require!(
+            tokens_bought <= anti_snipe.max_tokens_per_wallet,
+            CommcoinError::WalletCapExceeded
+        );
+
+        buyer_record.buyer = self.user.key();
+        buyer_record.bonding_curve = bonding_curve_key;
+        buyer_record.tokens_bought = tokens_bought;
+        buyer_record.last_buy_slot = slot;
+        buyer_record.bump = bump;
+        Ok(())
+    }
+}
+
+// Price and fees for taking `token_amount` off the curve
+pub(crate) struct BuyQuote {
+    pub token_amount: u64,
+    pub sol_cost: u64,    // goes into the curve reserves
+    pub fee: u64,         // platform fee, including the stakers' and referrer's shares
+    pub creator_fee: u64, // held by the curve account for the creator
+}
+
+impl BuyQuote {
+    pub fn new(curve: &BondingCurveState, config: &Config, token_amount: u64) -> Result<Self> {
+        let sol_cost = curve.get_buy_price(token_amount)?;
+        Ok(Self {
+            token_amount,
+            sol_cost,
+            fee: Config::calculate_fee(sol_cost, config.buy_fee_bps)?,
+            creator_fee: Config::calculate_fee(sol_cost, config.creator_fee_bps)?,
+        })
+    }
+
+    // Largest buy of at most `max_tokens` whose total cost fits in `sol_budget`.
+    // Starts from the inverse of the curve price, exact for the constant product curve and off
+    // by fixed point rounding for the others. The estimate is then checked against real quotes,
+    // stepping away from it until the answer is bracketed, so a buy costs a handful of quotes.
+    pub fn for_sol_budget(curve: &BondingCurveState, config: &Config, sol_budget: u64, max_tokens: u64) -> Result<Self> {
+        let max_tokens = curve.real_token_reserves.min(max_tokens);
+        require!(max_tokens > 0, CommcoinError::InvalidAmount);
+        let fits = |tokens: u64| -> Result<bool> {
+            if tokens == 0 {
+                return Ok(true);
+            }
+            Ok(match Self::new(curve, config, tokens) {
+                Ok(quote) => quote.total_cost()? <= sol_budget,
+                Err(_) => false,
+            })
+        };
+        // Budgets that clear the whole cap also keep the inverse below away from huge inputs
+        if fits(max_tokens)? {
+            return Self::new(curve, config, max_tokens);
+        }
+
+        let sol_cost = Self::max_sol_cost(config, sol_budget)?;
+        let estimate = curve.curve_type.pricing().tokens_for(curve, sol_cost)?.min(max_tokens - 1);
+
+        // fits(low) and !fits(high + 1) once bracketed
+        let (mut low, mut high) = if fits(estimate)? {
+            let (mut low, mut step) = (estimate, 1u64);
+            loop {
+                let next = low.saturating_add(step).min(max_tokens);
+                if !fits(next)? {
+                    break (low, next - 1);
+                }
+                low = next;
+                step = step.saturating_mul(2);
+            }
+        } else {
+            let (mut high, mut step) = (estimate - 1, 1u64);
+            loop {
+                let next = high.saturating_sub(step);
+                if fits(next)? {
+                    break (next, high);
+                }
+                high = next - 1;
+                step = step.saturating_mul(2);
+            }
+        };
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if fits(mid)? {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        require!(low > 0, CommcoinError::InvalidAmount);
+        Self::new(curve, config, low)
+    }
+
+    // Largest curve cost whose fees still fit in `sol_budget`
+    fn max_sol_cost(config: &Config, sol_budget: u64) -> Result<u64> {
+        let total_for = |sol_cost: u64| -> Result<u64> {
+            let fee = Config::calculate_fee(sol_cost, config.buy_fee_bps)?;
+            math::add(math::add(sol_cost, fee)?, Config::calculate_fee(sol_cost, config.creator_fee_bps)?)
+        };
+        let fee_bps = config.buy_fee_bps as u128 + config.creator_fee_bps as u128;
+        let mut sol_cost = math::to_u64(math::mul_div(sol_budget as u128, 10_000, 10_000 + fee_bps)?)?;
+        // Each fee is rounded down, which can leave room for a couple more lamports
+        while sol_cost < sol_budget && total_for(sol_cost + 1)? <= sol_budget {
+            sol_cost += 1;
+        }
+        Ok(sol_cost)
+    }
+
+    pub fn total_cost(&self) -> Result<u64> {
+        math::add(math::add(self.sol_cost, self.fee)?, self.creator_fee)
+    }
 
+    pub fn emit_trade(
+        &self,
+        mint: Pubkey,
+        user: Pubkey,
+        split: &FeeSplit,
+        referrer: Option<Pubkey>,
+        curve: &BondingCurveState,
+    ) -> Result<()> {
+        emit!(Trade {
+            mint,
+            user,
+            is_buy: true,
+            sol_amount: self.sol_cost,
+            token_amount: self.token_amount,
+            fee: self.fee,
+            staker_fee: split.staker_fee,
+            creator_fee: self.creator_fee,
+            referrer,
+            referrer_fee: split.referrer_fee,
+            virtual_sol_reserves: curve.virtual_sol_reserves,
+            virtual_token_reserves: curve.virtual_token_reserves,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
+    }
+}
+
+// Stops trading once the curve reaches the config limit.
+// Linear and exponential curves can sell out before reaching it.
+pub(crate) fn complete_if_needed(curve: &mut BondingCurveState, config: &Config, mint: Pubkey) {
+    if curve.real_sol_reserves >= config.curve_limit || curve.real_token_reserves == 0 {
+        curve.complete = true;
+
+        emit!(CurveCompleted {
+            mint,
+            real_sol_reserves: curve.real_sol_reserves,
+            real_token_reserves: curve.real_token_reserves,
+        });
+        msg!("Bonding curve complete: {}", mint);
+    }
+}
 
+// Where a trading fee goes, the three parts add up to the fee
+#[derive(Default)]
+pub(crate) struct FeeSplit {
+    pub platform_fee: u64,
+    pub staker_fee: u64,
+    pub referrer_fee: u64,
+}
+
+impl FeeSplit {
+    // Drops the payouts that would leave their recipient below the rent-exempt minimum,
+    // a transfer like that fails and would take the whole trade with it.
+    // An unpayable referrer share goes back to the platform, an unpayable platform share
+    // isn't charged at all. Returns the part of the fee that is no longer charged.
+    pub fn skip_unpayable(&mut self, fee_recipient: &AccountInfo, referrer: Option<&AccountInfo>, rent: &Rent) -> u64 {
+        if let Some(referrer) = referrer {
+            if !rent_safe_payout(referrer, self.referrer_fee, rent) {
+                self.platform_fee = self.platform_fee.saturating_add(self.referrer_fee);
+                self.referrer_fee = 0;
+            }
+        }
+        if rent_safe_payout(fee_recipient, self.platform_fee, rent) {
+            return 0;
+        }
+        std::mem::take(&mut self.platform_fee)
+    }
+}
+
+// Whether `account` is rent-exempt once paid `amount`, a zero payout never moves anything
+pub(crate) fn rent_safe_payout(account: &AccountInfo, amount: u64, rent: &Rent) -> bool {
+    amount == 0 || account.lamports().saturating_add(amount) >= rent.minimum_balance(account.data_len())
+}
+
+// Splits a trading fee between the vault stakers, the referrer and the platform.
+// Stakers take their share of the whole fee, the referrer a share of what is left for the platform.
+// With nobody staked the stakers' share goes to the platform.
+pub(crate) fn split_fee(fee: u64, config: &Config, vault: &VaultConfig, has_referrer: bool) -> Result<FeeSplit> {
+    let staker_fee = if vault.total_weight > 0 {
+        Config::calculate_fee(fee, config.staker_fee_share_bps)?
+    } else {
+        0
+    };
+    let platform_fee = math::sub(fee, staker_fee)?;
+    let referrer_fee = if has_referrer {
+        Config::calculate_fee(platform_fee, config.referral_fee_share_bps)?
+    } else {
+        0
+    };
+    Ok(FeeSplit {
+        platform_fee: math::sub(platform_fee, referrer_fee)?,
+        staker_fee,
+        referrer_fee,
+    })
+}
+
+// Referrer passed with a trade, wallets can't refer their own trades
+pub(crate) fn check_referrer(referrer: &Option<UncheckedAccount>, user: &Pubkey) -> Result<Option<Pubkey>> {
+    let Some(referrer) = referrer else {
+        return Ok(None);
+    };
+    require_keys_neq!(referrer.key(), *user, CommcoinError::InvalidReferrer);
+    Ok(Some(referrer.key()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_account<R>(lamports: u64, f: impl FnOnce(&AccountInfo) -> R) -> R {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::default();
+        let mut lamports = lamports;
+        let mut data = [];
+        f(&AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0))
+    }
+
+    fn split() -> FeeSplit {
+        FeeSplit { platform_fee: 9_000, staker_fee: 3_000, referrer_fee: 1_000 }
+    }
+
+    #[test]
+    fn referrer_with_no_lamports_hands_its_share_to_the_platform() {
+        let rent = Rent::default();
+        let mut split = split();
+        let waived = with_account(rent.minimum_balance(0), |fee_recipient| {
+            with_account(0, |referrer| split.skip_unpayable(fee_recipient, Some(referrer), &rent))
+        });
+        assert_eq!(waived, 0);
+        assert_eq!((split.platform_fee, split.staker_fee, split.referrer_fee), (10_000, 3_000, 0));
+    }
+
+    #[test]
+    fn referrer_paid_into_rent_exemption_keeps_its_share() {
+        let rent = Rent::default();
+        let mut split = FeeSplit { referrer_fee: rent.minimum_balance(0), ..split() };
+        with_account(rent.minimum_balance(0), |fee_recipient| {
+            with_account(0, |referrer| split.skip_unpayable(fee_recipient, Some(referrer), &rent))
+        });
+        assert_eq!(split.referrer_fee, rent.minimum_balance(0));
+        assert_eq!(split.platform_fee, 9_000);
+    }
+
+    #[test]
+    fn drained_fee_recipient_is_not_charged_for() {
+        let rent = Rent::default();
+        let mut split = split();
+        let waived = with_account(0, |fee_recipient| {
+            with_account(0, |referrer| split.skip_unpayable(fee_recipient, Some(referrer), &rent))
+        });
+        // the referrer's share went back to the platform before the platform share was dropped
+        assert_eq!(waived, 10_000);
+        assert_eq!((split.platform_fee, split.staker_fee, split.referrer_fee), (0, 3_000, 0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn zero_payouts_need_no_rent() {
+        let rent = Rent::default();
+        let mut split = FeeSplit { platform_fee: 0, staker_fee: 3_000, referrer_fee: 0 };
+        let waived = with_account(0, |fee_recipient| {
+            with_account(0, |referrer| split.skip_unpayable(fee_recipient, Some(referrer), &rent))
+        });
+        assert_eq!(waived, 0);
+        assert_eq!(split.staker_fee, 3_000);
+    }
+}
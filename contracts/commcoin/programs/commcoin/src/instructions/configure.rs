@@ -1,20 +1,28 @@
 use anchor_lang::{prelude::*, system_program};
 
-use crate::{states::Config};
+use crate::{errors::CommcoinError, program::Commcoin, seeds::CONFIG_SEED, states::{Config, Versioned}};
 
 #[derive(Accounts)]
 pub struct Configure<'info> {
     #[account(mut)]
-    admin: Signer<'info>,
+    admin: Signer<'info>, // Must be the program's upgrade authority
 
     #[account(
         init,
         payer = admin,
-        seeds = [b"commcoin_bonding_curve", b"commcoin_random_minted"],//[Config::SEED_PREFIX.as_bytes()],
+        seeds = [CONFIG_SEED],
         space = 8 + Config::LEN,
         bump,
     )]
-    mint_authority: Account<'info, Config>,
+    global_config: Account<'info, Config>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ CommcoinError::Unauthorized)]
+    program: Program<'info, Commcoin>,
+
+    // Only the upgrade authority of the deployed program may create the config,
+    // so nobody can front-run the first configure after deploy
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CommcoinError::Unauthorized)]
+    program_data: Account<'info, ProgramData>,
 
     #[account(address = system_program::ID)]
     system_program: Program<'info, System>,
@@ -22,11 +30,17 @@ pub struct Configure<'info> {
 
 impl<'info> Configure<'info> {
     pub fn process(&mut self, new_config: Config) -> Result<()> {
-        // require!(self.mint_authority.authority.eq(&Pubkey::default())
-        //     || self.mint_authority.authority.eq(&self.admin.key()), PumpError::NotAuthorized);
+        new_config.validate()?;
 
-        self.mint_authority.set_inner(new_config);
+        // the upgrade authority becomes the config authority, handover goes through nominate/accept
+        self.global_config.set_inner(Config {
+            version: Config::CURRENT_VERSION,
+            authority: self.admin.key(),
+            pending_authority: Pubkey::default(),
+            _reserved: Default::default(),
+            ..new_config
+        });
 
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,59 @@
+use anchor_lang::{prelude::*};
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    errors::CommcoinError,
+    math,
+    events::CreatorFeesClaimed,
+    seeds::BONDING_CURVE_SEED,
+    states::BondingCurveState,
+};
+
+
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>, // Coin creator, receives the lamports
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        has_one = creator @ CommcoinError::Unauthorized,
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+}
+
+impl<'info> ClaimCreatorFees<'info>{
+    pub fn claim_creator_fees(&mut self) -> Result<()> {
+        let amount = self.bonding_curve.creator_fees_accrued;
+        require!(amount > 0, CommcoinError::InvalidAmount);
+
+        // Creator fees are kept apart from real_sol_reserves, so paying them out
+        // never touches the curve liquidity or its rent-exempt balance
+        let curve_info = self.bonding_curve.to_account_info();
+        math::move_lamports(&curve_info, &self.creator.to_account_info(), amount)?;
+
+        let bonding_curve_state = &mut self.bonding_curve;
+        bonding_curve_state.creator_fees_accrued = 0;
+        bonding_curve_state.creator_fees_claimed = math::add(bonding_curve_state.creator_fees_claimed, amount)?;
+        bonding_curve_state.check_solvency(&curve_info)?;
+
+        emit!(CreatorFeesClaimed {
+            mint: self.mint.key(),
+            creator: self.creator.key(),
+            amount,
+            total_claimed: self.bonding_curve.creator_fees_claimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Instruction: Claim Creator Fees");
+        msg!("Creator: {}, lamports: {}", self.creator.key(), amount);
+
+        Ok(())
+    }
+}
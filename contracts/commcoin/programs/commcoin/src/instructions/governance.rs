@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    events::{ProposalCreated, ProposalFinalized, VoteCast},
+    math,
+    seeds::{CONFIG_SEED, PROPOSAL_SEED, USER_STAKE_INFO_SEED, VAULT_CONFIG_SEED, VOTE_RECORD_SEED},
+    states::{Config, Proposal, ProposalStatus, UserStakeInfo, VaultConfig, VaultError, VoteRecord, Versioned},
+};
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>, // Staker of the vault, pays rent for the proposal
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub global_config: Box<Account<'info, Config>>,
+
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut, // To bump proposal_count
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [USER_STAKE_INFO_SEED, proposer.key().as_ref(), vault.key().as_ref()],
+        bump = user_stake_info.bump,
+    )]
+    pub user_stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [PROPOSAL_SEED, vault.key().as_ref(), vault.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposal<'info> {
+    // Opens a proposal for `voting_period` seconds. The vault weight and the slot are
+    // snapshotted here, stakers vote with the weight they held then, see UserStakeInfo::vote_weight.
+    pub fn create_proposal(&mut self, title: String, uri: String, voting_period: i64, proposal_bump: u8) -> Result<()> {
+        let governance = &self.global_config.governance;
+        require!(
+            !title.is_empty()
+                && title.len() <= Proposal::MAX_TITLE_LEN
+                && uri.len() <= Proposal::MAX_URI_LEN
+                && voting_period >= governance.min_voting_period
+                && voting_period <= governance.max_voting_period,
+            VaultError::InvalidProposal
+        );
+
+        let vault = &mut self.vault;
+        let weight = self.user_stake_info.weighted_stake;
+        require!(
+            weight > 0 && weight >= math::bps(vault.total_weight, governance.min_proposer_weight_bps)?,
+            VaultError::InsufficientProposerWeight
+        );
+
+        let clock = Clock::get()?;
+        let id = vault.proposal_count;
+        vault.proposal_count = math::add(id, 1)?;
+
+        self.proposal.set_inner(Proposal {
+            version: Proposal::CURRENT_VERSION,
+            vault: vault.key(),
+            id,
+            proposer: self.proposer.key(),
+            title,
+            uri,
+            snapshot_slot: clock.slot,
+            snapshot_weight: vault.total_weight,
+            voting_ends_at: math::add_i64(clock.unix_timestamp, voting_period)?,
+            yes_weight: 0,
+            no_weight: 0,
+            status: ProposalStatus::Active,
+            bump: proposal_bump,
+            _reserved: Default::default(),
+        });
+
+        let proposal = &self.proposal;
+        emit!(ProposalCreated {
+            vault: proposal.vault,
+            proposal: proposal.key(),
+            id,
+            proposer: proposal.proposer,
+            title: proposal.title.clone(),
+            uri: proposal.uri.clone(),
+            snapshot_slot: proposal.snapshot_slot,
+            snapshot_weight: proposal.snapshot_weight,
+            voting_ends_at: proposal.voting_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Instruction: Create Proposal");
+        msg!("Vault: {}, proposal: {}, ends at: {}", proposal.vault, id, proposal.voting_ends_at);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>, // Pays rent for the vote record
+
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [USER_STAKE_INFO_SEED, voter.key().as_ref(), vault.key().as_ref()],
+        bump = user_stake_info.bump,
+    )]
+    pub user_stake_info: Account<'info, UserStakeInfo>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = vault,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    // Exists once the voter has voted, a second vote fails on init
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CastVote<'info> {
+    pub fn cast_vote(&mut self, support: bool, vote_record_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.proposal.is_open(now), VaultError::VotingClosed);
+
+        let weight = self.user_stake_info.vote_weight(self.proposal.snapshot_slot);
+        require!(weight > 0, VaultError::NoVotingPower);
+
+        self.proposal.record_vote(support, weight)?;
+        self.vote_record.set_inner(VoteRecord {
+            proposal: self.proposal.key(),
+            voter: self.voter.key(),
+            support,
+            weight,
+            bump: vote_record_bump,
+        });
+
+        emit!(VoteCast {
+            vault: self.vault.key(),
+            proposal: self.proposal.key(),
+            voter: self.voter.key(),
+            support,
+            weight,
+            yes_weight: self.proposal.yes_weight,
+            no_weight: self.proposal.no_weight,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Cast Vote");
+        msg!("Proposal: {}, voter: {}, support: {}, weight: {}", self.proposal.id, self.voter.key(), support, weight);
+
+        Ok(())
+    }
+}
+
+// Permissionless, settles a proposal once its voting period is over
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub global_config: Box<Account<'info, Config>>,
+
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, vault.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = vault,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+impl<'info> FinalizeProposal<'info> {
+    pub fn finalize_proposal(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.proposal.status == ProposalStatus::Active, VaultError::VotingClosed);
+        require!(now >= self.proposal.voting_ends_at, VaultError::VotingNotEnded);
+
+        self.proposal.status = self.proposal.outcome(&self.global_config)?;
+
+        emit!(ProposalFinalized {
+            vault: self.vault.key(),
+            proposal: self.proposal.key(),
+            status: self.proposal.status,
+            yes_weight: self.proposal.yes_weight,
+            no_weight: self.proposal.no_weight,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Finalize Proposal");
+        msg!("Proposal: {}, status: {:?}", self.proposal.id, self.proposal.status);
+
+        Ok(())
+    }
+}
@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    instruction::{AccountMeta, Instruction},
-    program::invoke, // Import invoke
-    pubkey::Pubkey,
-};
+
+use anchor_lang::system_program::{transfer as sol_transfer, Transfer as SOLTransfer};
 
 use anchor_spl::{
-    associated_token::{Create as CreateIdempotent, AssociatedToken, get_associated_token_address, create_idempotent},
-    token::{mint_to, set_authority, MintTo, Mint, Token, TokenAccount, SetAuthority, spl_token::instruction::AuthorityType},
+    associated_token::{create as create_ata, get_associated_token_address_with_program_id, AssociatedToken, Create as CreateAta},
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        Metadata,
+    },
+    token::{accessor as token_accessor, Mint, Token, TokenAccount},
+    token_interface::{
+        mint_to, set_authority, transfer_checked, spl_token_2022::instruction::AuthorityType,
+        MintTo, SetAuthority, TransferChecked,
+    },
 };
 
 // use spl_associated_token_account::instruction::create_associated_token_account;
@@ -18,21 +23,28 @@ use anchor_spl::{
 // use commcoin::program::Commcoin;
 
 use crate::{
-    states::{Config, BondingCurveState, VaultConfig},
+    errors::CommcoinError,
+    events::CoinCreated,
+    instructions::buy::{complete_if_needed, BuyQuote, FeeSplit},
+    math,
+    seeds::{
+        BONDING_CURVE_SEED, BUYER_RECORD_SEED, CONFIG_SEED, CREATOR_VESTING_SEED, MINT_AUTHORITY_SEED, VAULT_CONFIG_SEED,
+        VAULT_TOKEN_SEED,
+    },
+    states::{
+        AllowList, AntiSnipeParams, BuyerRecord, Config, BondingCurveState, CreatorVesting, CurveType, LaunchParams,
+        VaultConfig, VaultError, VestingArgs,
+    },
 };
 
 // use crate::instructions::ExtendAccount;
-use std::io::Write; 
 
-const TOKEN_DECIMALS: u8 = 6; // Example: 9 decimals for 1B supply
-const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
-const TOTAL_SUPPLY: u64 = 1_000_000_000 * (10u64.pow(TOKEN_DECIMALS as u32)); // 1 Billion base units
-const PLATFORM_FEE_BPS: u16 = 100; // Basis points (100 = 1%)
-const INITIAL_SOL_RESERVE: f64 = 29.916123;
 
-const VAULT_CONFIG_SEED: &[u8] = b"vault_config_v1";
-const VAULT_TOKEN_SEED: &[u8] = b"vault_tokens_v1";
-const USER_STAKE_INFO_SEED: &[u8] = b"user_stake_v1";
+// Limits enforced by the token metadata program
+const MAX_NAME_LENGTH: usize = 32;
+const MAX_SYMBOL_LENGTH: usize = 10;
+const MAX_URI_LENGTH: usize = 200;
+
 
 // !!! IMPORTANT: REPLACE THIS WITH THE ACTUAL PUBLIC KEY THAT SHOULD BE THE FINAL MINT AUTHORITY !!!
 // This is a placeholder address (the default system program ID).
@@ -40,28 +52,129 @@ const USER_STAKE_INFO_SEED: &[u8] = b"user_stake_v1";
 // const FIXED_MINT_AUTHORITY_PUBKEY: Pubkey = pubkey!("EJdcnkTKogTPuRDj3QZMYNUxCinjtYnERzmk65yjvDkC");
 
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCoinArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub vault_name: String, // community vault created alongside the coin
+    pub vault_goal: u64,    // tokens to be staked for the vault goal
+    pub curve_type: CurveType, // must be allowed by the config
+    pub launch_params: Option<LaunchParams>, // platform defaults when None
+    pub initial_buy_sol: Option<u64>, // lamports the creator spends on a first buy, fees included
+    pub anti_snipe: Option<AntiSnipeParams>, // config defaults when None
+    pub trading_starts_at: Option<i64>, // unix timestamp, trading opens at launch when None
+    pub allow_list: Option<AllowList>,  // wallets allowed to buy first
+    pub creator_vesting: Option<VestingArgs>, // allocation minted to the creator vesting account, none when None
+}
+
+impl CreateCoinArgs {
+    // Launch parameters for this coin, creator supplied ones are checked by `validate`
+    pub fn launch_params(&self, config: &Config) -> LaunchParams {
+        self.launch_params.unwrap_or_else(|| config.default_launch_params())
+    }
+
+    pub fn validate(&self, config: &Config) -> Result<()> {
+        if let Some(params) = &self.launch_params {
+            config.validate_launch_params(params)?;
+        }
+        require!(
+            config.is_curve_allowed(self.curve_type),
+            CommcoinError::CurveTypeNotAllowed
+        );
+        validate_metadata(&self.name, &self.symbol, &self.uri)?;
+        require!(
+            !self.vault_name.is_empty() && self.vault_name.len() <= VaultConfig::MAX_NAME_LEN,
+            VaultError::InvalidVaultName
+        );
+        require!(self.vault_goal > 0, VaultError::GoalCannotBeZero);
+        require!(self.initial_buy_sol != Some(0), CommcoinError::InvalidAmount);
+        if let Some(vesting) = &self.creator_vesting {
+            let launch_at = match self.trading_starts_at {
+                Some(starts_at) => starts_at,
+                None => Clock::get()?.unix_timestamp,
+            };
+            vesting.validate(config, launch_at)?;
+        }
+        Ok(())
+    }
+}
+
+// Name, symbol and uri within the token metadata program limits, also used by update_metadata
+pub(crate) fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_NAME_LENGTH,
+        CommcoinError::InvalidMetadata
+    );
+    require!(
+        !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LENGTH,
+        CommcoinError::InvalidMetadata
+    );
+    require!(uri.len() <= MAX_URI_LENGTH, CommcoinError::InvalidMetadata);
+    Ok(())
+}
+
+// Sets up the creator vesting account when the launch has a vesting schedule and returns
+// the allocation to mint into it. The optional vesting accounts must match `args`.
+pub(crate) fn init_creator_vesting(
+    args: &CreateCoinArgs,
+    launch_params: &LaunchParams,
+    creator_vesting: Option<&mut CreatorVesting>,
+    has_vesting_token_account: bool,
+    mint: Pubkey,
+    creator: Pubkey,
+    creator_vesting_bump: Option<u8>,
+) -> Result<u64> {
+    match (&args.creator_vesting, creator_vesting, creator_vesting_bump) {
+        (Some(vesting_args), Some(creator_vesting), Some(bump)) if has_vesting_token_account => {
+            let allocation = vesting_args.allocation(launch_params.token_total_supply)?;
+            require!(allocation > 0, CommcoinError::InvalidVestingSchedule);
+            creator_vesting.initialize(vesting_args, mint, creator, allocation, bump);
+            Ok(allocation)
+        }
+        (None, None, _) if !has_vesting_token_account => Ok(0),
+        _ => err!(CommcoinError::VestingAccountsMismatch),
+    }
+}
+
 #[derive(Accounts)]
+#[instruction(args: CreateCoinArgs)]
 pub struct CreateCoinInfo<'info> {
     // Accounts for creating the token & metadata
     #[account(mut)]
     pub signer: Signer<'info>, // User creating the token, pays rent
 
     #[account(
-        mut,
-        seeds = [b"commcoin_bonding_curve", b"commcoin_random_minted"], // Seed with mint for uniqueness
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, Config>,
+
+    /// CHECK: data-less PDA of this coin, temporary mint authority and permanent metadata update authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
         bump
     )]
-    pub mint_authority: Account<'info, Config>,
+    pub mint_authority: UncheckedAccount<'info>,
 
     #[account(
         init,
         payer = signer,
-        mint::decimals = TOKEN_DECIMALS,
+        mint::decimals = args.launch_params(&global_config).decimals,
         mint::authority = mint_authority.key(), // Payer is TEMP mint authority
         // mint::creator = signer.key(),
     )]
     pub mint: Account<'info, Mint>,
 
+    /// CHECK: created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = signer,
@@ -87,7 +200,7 @@ pub struct CreateCoinInfo<'info> {
         payer = signer,
         space = BondingCurveState::LEN,
         owner = crate::ID,
-        seeds = [b"commcoin_bonding_curve", mint.key().as_ref()], // Seed with mint for uniqueness
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()], // Seed with mint for uniqueness
         bump
     )]
     pub bonding_curve: Account<'info, BondingCurveState>,
@@ -101,9 +214,51 @@ pub struct CreateCoinInfo<'info> {
     )]
     pub bonding_curve_token_vault: Account<'info, TokenAccount>,
 
-    
+    // Only with `args.creator_vesting`, releases the creator allocation over time
+    #[account(
+        init,
+        payer = signer,
+        space = CreatorVesting::LEN,
+        seeds = [CREATOR_VESTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub creator_vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    // Only with `args.creator_vesting`, holds the allocation until it is claimed
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = creator_vesting,
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: creator's associated token account, only needed and created with an initial buy
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&signer.key(), &mint.key(), &token_program.key())
+    )]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    // Only with an initial buy on a coin with a protected launch window, counts it toward the creator's cap
+    #[account(
+        init,
+        payer = signer,
+        space = BuyerRecord::LEN,
+        seeds = [BUYER_RECORD_SEED, bonding_curve.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub creator_buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+
+    /// CHECK: only receives lamports, address is checked against the config
+    #[account(
+        mut,
+        address = global_config.fee_recipient @ CommcoinError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -112,60 +267,320 @@ pub struct CreateCoinInfo<'info> {
 
 impl<'info> CreateCoinInfo<'info>{
 
-    pub fn create_coin(&mut self, bump_config: u8, vault_bump: u8, vault_token_bump: u8) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_coin(
+        &mut self,
+        args: CreateCoinArgs,
+        mint_authority_bump: u8,
+        vault_bump: u8,
+        vault_token_bump: u8,
+        bonding_curve_bump: u8,
+        creator_vesting_bump: Option<u8>,
+        creator_buyer_record_bump: Option<u8>,
+    ) -> Result<()> {
+        args.validate(&self.global_config)?;
 
-        let bonding_curve_state = &mut self.bonding_curve;
+        let launch_params = args.launch_params(&self.global_config);
+        let creator_allocation = init_creator_vesting(
+            &args,
+            &launch_params,
+            self.creator_vesting.as_deref_mut().map(|vesting| &mut **vesting),
+            self.vesting_token_account.is_some(),
+            self.mint.key(),
+            self.signer.key(),
+            creator_vesting_bump,
+        )?;
+        let anti_snipe = self
+            .global_config
+            .anti_snipe_params(args.anti_snipe, launch_params.token_total_supply)?;
+        self.bonding_curve.initialize(
+            &self.global_config,
+            &launch_params,
+            self.signer.key(),
+            args.curve_type,
+            anti_snipe,
+            creator_allocation,
+        )?;
+        self.bonding_curve.set_schedule(args.trading_starts_at, args.allow_list)?;
+        self.vault.initialize(
+            args.vault_name.clone(),
+            args.vault_goal,
+            self.signer.key(),
+            self.mint.key(),
+            self.vault_token_account.key(),
+            vault_bump,
+            vault_token_bump,
+        );
 
-        bonding_curve_state.real_token_reserves = TOTAL_SUPPLY;
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, mint_pubkey.as_ref(), &[mint_authority_bump]]];
 
-        bonding_curve_state.virtual_token_reserves = TOTAL_SUPPLY;
-        bonding_curve_state.virtual_sol_reserves = (INITIAL_SOL_RESERVE * LAMPORTS_PER_SOL) as u64;
-        let vault_name = "Stacked Community";
+        // The coin's mint authority PDA is also the permanent update authority,
+        // so metadata can only be changed through this program, see update_metadata
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: self.metadata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    mint_authority: self.mint_authority.to_account_info(),
+                    payer: self.signer.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer_seeds
+            ),
+            DataV2 {
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )?;
 
-        let vault_config = &mut self.vault;
-        vault_config.name = vault_name.to_string();
-        vault_config.authority = self.signer.key();
-        vault_config.token_mint = self.mint.key();
-        vault_config.vault_token_account = self.vault_token_account.key();
-        vault_config.goal_tokens_to_reach = 80;
-        vault_config.current_tokens_staked = 0;
-        vault_config.goal_reached = false;
-        vault_config.bump_config = vault_bump;
-        vault_config.bump_token_vault = vault_token_bump;
+        mint_supply_and_revoke(
+            self.token_program.to_account_info(),
+            self.mint.to_account_info(),
+            self.mint_authority.to_account_info(),
+            signer_seeds,
+            (self.bonding_curve_token_vault.to_account_info(), math::sub(self.bonding_curve.token_total_supply, creator_allocation)?),
+            self.vesting_token_account.as_ref().map(|account| (account.to_account_info(), creator_allocation)),
+        )?;
 
+        emit!(CoinCreated {
+            mint: self.mint.key(),
+            creator: self.signer.key(),
+            bonding_curve: self.bonding_curve.key(),
+            vault: self.vault.key(),
+            token_total_supply: self.bonding_curve.token_total_supply,
+            decimals: launch_params.decimals,
+            virtual_sol_reserves: self.bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: self.bonding_curve.virtual_token_reserves,
+            real_token_reserves: self.bonding_curve.real_token_reserves,
+            curve_type: self.bonding_curve.curve_type,
+            creator_allocation,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        let signer_seeds: &[&[&[u8]]] = &[&[b"commcoin_bonding_curve", b"commcoin_random_minted", &[bump_config]]];
+        if let Some(sol_budget) = args.initial_buy_sol {
+            let creator_token_account = self
+                .creator_token_account
+                .as_ref()
+                .ok_or(CommcoinError::CreatorTokenAccountRequired)?;
+            InitialBuy {
+                creator: self.signer.to_account_info(),
+                mint: self.mint.to_account_info(),
+                bonding_curve: self.bonding_curve.to_account_info(),
+                bonding_curve_token_vault: self.bonding_curve_token_vault.to_account_info(),
+                creator_token_account: creator_token_account.to_account_info(),
+                fee_recipient: self.fee_recipient.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            }
+            .execute(
+                &mut self.bonding_curve,
+                &self.global_config,
+                sol_budget,
+                launch_params.decimals,
+                bonding_curve_bump,
+                self.creator_buyer_record.as_deref_mut().map(|record| &mut **record).zip(creator_buyer_record_bump),
+            )?;
+        } else {
+            // Without an initial buy the fresh curve is checked here, InitialBuy checks its own result
+            self.bonding_curve.check_invariants(
+                &self.bonding_curve.to_account_info(),
+                token_accessor::amount(&self.bonding_curve_token_vault.to_account_info())?,
+            )?;
+        }
 
-        mint_to(
+        msg!("Instruction: Create Token With Curve");
+        msg!("Signer: {}", self.signer.key());
+        msg!("New Mint: {}", self.mint.key());
+        
+        Ok(())
+    }
+}
+
+// Creator's first buy, run inside create_coin so nobody can trade between launch and purchase.
+// Same pricing and fees as `buy`, the vault has no stakers yet so the whole fee goes to the platform.
+// It is the creator's own launch purchase, so `trading_starts_at` and the allow-list don't apply to it.
+// The protected window's wallet cap does, the buy is recorded in the creator's BuyerRecord.
+pub(crate) struct InitialBuy<'info> {
+    pub creator: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub bonding_curve: AccountInfo<'info>,
+    pub bonding_curve_token_vault: AccountInfo<'info>,
+    pub creator_token_account: AccountInfo<'info>,
+    pub fee_recipient: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> InitialBuy<'info> {
+    // The coin's per-wallet cap when it has a protected window, otherwise only the curve reserves bound the buy
+    fn max_tokens(curve: &BondingCurveState) -> u64 {
+        if curve.anti_snipe.protected_slots > 0 {
+            curve.anti_snipe.max_tokens_per_wallet
+        } else {
+            u64::MAX
+        }
+    }
+
+    pub fn execute(
+        self,
+        curve: &mut BondingCurveState,
+        config: &Config,
+        sol_budget: u64,
+        decimals: u8,
+        bonding_curve_bump: u8,
+        buyer_record: Option<(&mut BuyerRecord, u8)>,
+    ) -> Result<()> {
+        let mut quote = BuyQuote::for_sol_budget(curve, config, sol_budget, Self::max_tokens(curve))?;
+        let mut split = FeeSplit { platform_fee: quote.fee, ..Default::default() };
+        quote.fee = math::sub(quote.fee, split.skip_unpayable(&self.fee_recipient, None, &Rent::get()?))?;
+
+        create_ata(CpiContext::new(
+            self.associated_token_program,
+            CreateAta {
+                payer: self.creator.clone(),
+                associated_token: self.creator_token_account.clone(),
+                authority: self.creator.clone(),
+                mint: self.mint.clone(),
+                system_program: self.system_program.clone(),
+                token_program: self.token_program.clone(),
+            },
+        ))?;
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[BONDING_CURVE_SEED, mint_pubkey.as_ref(), &[bonding_curve_bump]]];
+        transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                MintTo {
-                    mint: self.mint.to_account_info(),
-                    to: self.bonding_curve_token_vault.to_account_info(),
-                    authority: self.mint_authority.to_account_info(), // Payer signs as temp mint authority
+                self.token_program,
+                TransferChecked {
+                    from: self.bonding_curve_token_vault.clone(),
+                    mint: self.mint,
+                    to: self.creator_token_account,
+                    authority: self.bonding_curve.clone(),
                 },
                 signer_seeds
             ),
-            TOTAL_SUPPLY,
+            quote.token_amount,
+            decimals,
+        )?;
+
+        // The creator fee is held by the curve account until claimed
+        let curve_amount = math::add(quote.sol_cost, quote.creator_fee)?;
+        sol_transfer(
+            CpiContext::new(
+                self.system_program.clone(),
+                SOLTransfer {
+                    from: self.creator.clone(),
+                    to: self.bonding_curve.clone(),
+                }
+            ),
+            curve_amount,
         )?;
+        if quote.fee > 0 {
+            sol_transfer(
+                CpiContext::new(
+                    self.system_program,
+                    SOLTransfer {
+                        from: self.creator.clone(),
+                        to: self.fee_recipient,
+                    }
+                ),
+                quote.fee,
+            )?;
+        }
+
+        curve.apply_buy(quote.token_amount, quote.sol_cost)?;
+        curve.accrue_creator_fee(quote.creator_fee)?;
+        curve.check_invariants(&self.bonding_curve, token_accessor::amount(&self.bonding_curve_token_vault)?)?;
+
+        // Later buys in the window see the tokens already taken, see Buy::check_launch_window
+        if curve.anti_snipe.protected_slots > 0 {
+            let (record, bump) = buyer_record.ok_or(CommcoinError::BuyerRecordRequired)?;
+            record.buyer = self.creator.key();
+            record.bonding_curve = self.bonding_curve.key();
+            record.tokens_bought = quote.token_amount;
+            record.last_buy_slot = Clock::get()?.slot;
+            record.bump = bump;
+        }
+
+        quote.emit_trade(mint_pubkey, self.creator.key(), &split, None, curve)?;
+        complete_if_needed(curve, config, mint_pubkey);
 
-        set_authority(
+        msg!("Initial buy: {} tokens for {} lamports", quote.token_amount, quote.total_cost()?);
+
+        Ok(())
+    }
+}
+// Mints the supply into the curve vault and the creator allocation, if any, into its
+// vesting account, then drops the mint authority for good.
+// Works for both token programs, the program id is taken from `token_program`.
+pub(crate) fn mint_supply_and_revoke<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    curve_supply: (AccountInfo<'info>, u64),
+    creator_allocation: Option<(AccountInfo<'info>, u64)>,
+) -> Result<()> {
+    for (to, amount) in std::iter::once(curve_supply).chain(creator_allocation) {
+        mint_to(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                SetAuthority {
-                    current_authority: self.mint_authority.to_account_info(), // The account that currently HAS mint authority
-                    account_or_mint: self.mint.to_account_info(), // The mint account whose authority we are changing
+                token_program.clone(),
+                MintTo {
+                    mint: mint.clone(),
+                    to,
+                    authority: mint_authority.clone(), // PDA signs as temp mint authority
                 },
                 signer_seeds
             ),
-            AuthorityType::MintTokens, 
-            None
+            amount,
         )?;
+    }
 
-        msg!("Instruction: Create Token With Curve");
-        msg!("Signer: {}", self.signer.key());
-        msg!("New Mint: {}", self.mint.key());
-        
-        Ok(())
+    set_authority(
+        CpiContext::new_with_signer(
+            token_program,
+            SetAuthority {
+                current_authority: mint_authority, // The account that currently HAS mint authority
+                account_or_mint: mint, // The mint account whose authority we are changing
+            },
+            signer_seeds
+        ),
+        AuthorityType::MintTokens,
+        None
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(protected_slots: u64, max_tokens_per_wallet: u64) -> BondingCurveState {
+        BondingCurveState {
+            real_token_reserves: 800_000,
+            anti_snipe: AntiSnipeParams { protected_slots, max_tokens_per_wallet, cooldown_slots: 0 },
+            ..Default::default()
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn initial_buy_is_only_capped_inside_a_protected_window() {
+        // No window, e.g. a config with max_wallet_bps = 0, leaves only the reserves
+        assert_eq!(InitialBuy::max_tokens(&curve(0, 0)), u64::MAX);
+        assert_eq!(InitialBuy::max_tokens(&curve(0, 10_000)), u64::MAX);
+        assert_eq!(InitialBuy::max_tokens(&curve(150, 10_000)), 10_000);
+    }
+}
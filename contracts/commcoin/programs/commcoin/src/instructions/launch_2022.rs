@@ -0,0 +1,364 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use anchor_spl::{
+    associated_token::{create as create_ata, get_associated_token_address_with_program_id, AssociatedToken, Create as CreateAta},
+    token::accessor as token_accessor,
+    token_2022::{initialize_mint2, InitializeMint2, Token2022},
+    token_interface::{
+        metadata_pointer_initialize, spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_2022::{extension::ExtensionType, state::Mint as MintState},
+        spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize,
+        transfer_fee_initialize, MetadataPointerInitialize, TokenMetadataInitialize,
+        TransferFeeInitialize,
+    },
+};
+
+use crate::{
+    errors::CommcoinError,
+    events::CoinCreated,
+    instructions::launch::{init_creator_vesting, mint_supply_and_revoke, CreateCoinArgs, InitialBuy},
+    math,
+    seeds::{BONDING_CURVE_SEED, BUYER_RECORD_SEED, CONFIG_SEED, CREATOR_VESTING_SEED, MINT_AUTHORITY_SEED, VAULT_CONFIG_SEED},
+    states::{BuyerRecord, Config, BondingCurveState, CreatorVesting, VaultConfig},
+};
+
+// Optional Token-2022 transfer fee, withheld amounts are claimable by the creator as a royalty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64, // cap per transfer, in base units
+}
+
+
+#[derive(Accounts)]
+pub struct CreateCoin2022Info<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>, // User creating the token, pays rent
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, Config>,
+
+    /// CHECK: data-less PDA of this coin, temporary mint authority and permanent metadata update authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    // Created in the handler, extensions have to be initialized before the mint itself
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultConfig::LEN,
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    /// CHECK: associated token account of the vault PDA, created in the handler
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&vault.key(), &mint.key(), &token_program.key())
+    )]
+    pub vault_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = BondingCurveState::LEN,
+        owner = crate::ID,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+
+    /// CHECK: associated token account of the bonding curve PDA, created in the handler
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&bonding_curve.key(), &mint.key(), &token_program.key())
+    )]
+    pub bonding_curve_token_vault: UncheckedAccount<'info>,
+
+    // Only with `args.creator_vesting`, releases the creator allocation over time
+    #[account(
+        init,
+        payer = signer,
+        space = CreatorVesting::LEN,
+        seeds = [CREATOR_VESTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub creator_vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    /// CHECK: associated token account of the creator vesting PDA, created in the handler,
+    /// the associated token program rejects any other address
+    #[account(mut)]
+    pub vesting_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: creator's associated token account, only needed and created with an initial buy
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&signer.key(), &mint.key(), &token_program.key())
+    )]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    // Only with an initial buy on a coin with a protected launch window, counts it toward the creator's cap
+    #[account(
+        init,
+        payer = signer,
+        space = BuyerRecord::LEN,
+        seeds = [BUYER_RECORD_SEED, bonding_curve.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub creator_buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+
+    /// CHECK: only receives lamports, address is checked against the config
+    #[account(
+        mut,
+        address = global_config.fee_recipient @ CommcoinError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateCoin2022Info<'info>{
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_coin_2022(
+        &mut self,
+        args: CreateCoinArgs,
+        transfer_fee: Option<TransferFeeArgs>,
+        mint_authority_bump: u8,
+        vault_bump: u8,
+        bonding_curve_bump: u8,
+        creator_vesting_bump: Option<u8>,
+        creator_buyer_record_bump: Option<u8>,
+    ) -> Result<()> {
+        args.validate(&self.global_config)?;
+        if let Some(fee) = &transfer_fee {
+            require!(fee.basis_points <= Config::MAX_FEE_BPS, CommcoinError::InvalidTransferFee);
+        }
+
+        let launch_params = args.launch_params(&self.global_config);
+        let creator_allocation = init_creator_vesting(
+            &args,
+            &launch_params,
+            self.creator_vesting.as_deref_mut().map(|vesting| &mut **vesting),
+            self.vesting_token_account.is_some(),
+            self.mint.key(),
+            self.signer.key(),
+            creator_vesting_bump,
+        )?;
+        let anti_snipe = self
+            .global_config
+            .anti_snipe_params(args.anti_snipe, launch_params.token_total_supply)?;
+        self.bonding_curve.initialize(
+            &self.global_config,
+            &launch_params,
+            self.signer.key(),
+            args.curve_type,
+            anti_snipe,
+            creator_allocation,
+        )?;
+        self.bonding_curve.set_schedule(args.trading_starts_at, args.allow_list)?;
+        self.vault.initialize(
+            args.vault_name.clone(),
+            args.vault_goal,
+            self.signer.key(),
+            self.mint.key(),
+            self.vault_token_account.key(),
+            vault_bump,
+            0, // vault tokens live in an associated token account, no PDA bump
+        );
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, mint_pubkey.as_ref(), &[mint_authority_bump]]];
+        let mint_authority_key = self.mint_authority.key();
+
+        // 1. Allocate the mint with room for its extensions. The token metadata entry is
+        //    reallocated by the token program later, so its rent is paid up front.
+        let mut extensions = vec![ExtensionType::MetadataPointer];
+        if transfer_fee.is_some() {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        let mint_space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(mint_authority_key))?,
+            mint: self.mint.key(),
+            name: args.name.clone(),
+            symbol: args.symbol.clone(),
+            uri: args.uri.clone(),
+            additional_metadata: vec![],
+        };
+        let metadata_space = token_metadata.tlv_size_of()?;
+        let lamports = self.rent.minimum_balance(mint_space + metadata_space);
+
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.signer.to_account_info(),
+                    to: self.mint.to_account_info(),
+                },
+            ),
+            lamports,
+            mint_space as u64,
+            &self.token_program.key(),
+        )?;
+
+        // 2. Extensions, before the mint is initialized
+        if let Some(fee) = transfer_fee {
+            let creator = self.signer.key();
+            transfer_fee_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferFeeInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                None, // fee can never be raised after launch
+                Some(&creator), // creator harvests withheld fees as royalty
+                fee.basis_points,
+                fee.maximum_fee,
+            )?;
+        }
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            Some(mint_authority_key),
+            Some(self.mint.key()), // metadata lives on the mint itself
+        )?;
+
+        // 3. The mint, with the coin's mint authority PDA as temporary mint authority
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            launch_params.decimals,
+            &mint_authority_key,
+            None,
+        )?;
+
+        // 4. Token metadata, the mint authority PDA stays update authority like on the Metaplex path
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.mint.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                    mint_authority: self.mint_authority.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer_seeds
+            ),
+            args.name,
+            args.symbol,
+            args.uri,
+        )?;
+
+        // 5. Token accounts for the curve, the community vault and the creator vesting
+        let mut token_accounts = vec![
+            (self.bonding_curve_token_vault.to_account_info(), self.bonding_curve.to_account_info()),
+            (self.vault_token_account.to_account_info(), self.vault.to_account_info()),
+        ];
+        if let (Some(token_account), Some(vesting)) = (&self.vesting_token_account, &self.creator_vesting) {
+            token_accounts.push((token_account.to_account_info(), vesting.to_account_info()));
+        }
+        for (associated_token, authority) in token_accounts {
+            create_ata(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                CreateAta {
+                    payer: self.signer.to_account_info(),
+                    associated_token,
+                    authority,
+                    mint: self.mint.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        mint_supply_and_revoke(
+            self.token_program.to_account_info(),
+            self.mint.to_account_info(),
+            self.mint_authority.to_account_info(),
+            signer_seeds,
+            (self.bonding_curve_token_vault.to_account_info(), math::sub(self.bonding_curve.token_total_supply, creator_allocation)?),
+            self.vesting_token_account.as_ref().map(|account| (account.to_account_info(), creator_allocation)),
+        )?;
+
+        emit!(CoinCreated {
+            mint: self.mint.key(),
+            creator: self.signer.key(),
+            bonding_curve: self.bonding_curve.key(),
+            vault: self.vault.key(),
+            token_total_supply: self.bonding_curve.token_total_supply,
+            decimals: launch_params.decimals,
+            virtual_sol_reserves: self.bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: self.bonding_curve.virtual_token_reserves,
+            real_token_reserves: self.bonding_curve.real_token_reserves,
+            curve_type: self.bonding_curve.curve_type,
+            creator_allocation,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if let Some(sol_budget) = args.initial_buy_sol {
+            let creator_token_account = self
+                .creator_token_account
+                .as_ref()
+                .ok_or(CommcoinError::CreatorTokenAccountRequired)?;
+            InitialBuy {
+                creator: self.signer.to_account_info(),
+                mint: self.mint.to_account_info(),
+                bonding_curve: self.bonding_curve.to_account_info(),
+                bonding_curve_token_vault: self.bonding_curve_token_vault.to_account_info(),
+                creator_token_account: creator_token_account.to_account_info(),
+                fee_recipient: self.fee_recipient.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            }
+            .execute(
+                &mut self.bonding_curve,
+                &self.global_config,
+                sol_budget,
+                launch_params.decimals,
+                bonding_curve_bump,
+                self.creator_buyer_record.as_deref_mut().map(|record| &mut **record).zip(creator_buyer_record_bump),
+            )?;
+        } else {
+            // Without an initial buy the fresh curve is checked here, InitialBuy checks its own result
+            self.bonding_curve.check_invariants(
+                &self.bonding_curve.to_account_info(),
+                token_accessor::amount(&self.bonding_curve_token_vault.to_account_info())?,
+            )?;
+        }
+
+        msg!("Instruction: Create Token-2022 With Curve");
+        msg!("Signer: {}", self.signer.key());
+        msg!("New Mint: {}", self.mint.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer as sol_transfer, Transfer as SOLTransfer};
+
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::types::DataV2, update_metadata_accounts_v2, Metadata, UpdateMetadataAccountsV2,
+    },
+    token::Mint,
+    token_2022::Token2022,
+    token_interface::{
+        spl_token_2022::{
+            extension::{BaseStateWithExtensions, StateWithExtensions},
+            state::Mint as MintState,
+        },
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_update_field, Mint as MintInterface, TokenMetadataUpdateField,
+    },
+};
+
+use crate::{
+    errors::CommcoinError,
+    events::MetadataUpdated,
+    instructions::launch::validate_metadata,
+    seeds::{BONDING_CURVE_SEED, MINT_AUTHORITY_SEED},
+    states::BondingCurveState,
+};
+
+// Metaplex metadata of a classic SPL coin, the mint authority PDA signs as update authority
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    pub creator: Signer<'info>, // Coin creator, only one who can change the metadata
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        has_one = creator @ CommcoinError::Unauthorized,
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+
+    /// CHECK: data-less PDA of this coin, update authority of its metadata
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> UpdateMetadata<'info> {
+    pub fn update_metadata(&mut self, name: String, symbol: String, uri: String, mint_authority_bump: u8) -> Result<()> {
+        validate_metadata(&name, &symbol, &uri)?;
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, mint_pubkey.as_ref(), &[mint_authority_bump]]];
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: self.metadata.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                },
+                signer_seeds
+            ),
+            None,
+            Some(DataV2 {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            }),
+            None,
+            None,
+        )?;
+
+        emit!(MetadataUpdated {
+            mint: mint_pubkey,
+            creator: self.creator.key(),
+            name,
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Instruction: Update Metadata");
+        msg!("Mint: {}", mint_pubkey);
+
+        Ok(())
+    }
+}
+
+// Token metadata stored on a Token-2022 coin's mint, same authority as the Metaplex path
+#[derive(Accounts)]
+pub struct UpdateMetadata2022<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>, // Coin creator, pays the rent of a longer metadata entry
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        has_one = creator @ CommcoinError::Unauthorized,
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+
+    /// CHECK: data-less PDA of this coin, update authority of its metadata
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateMetadata2022<'info> {
+    pub fn update_metadata(&mut self, name: String, symbol: String, uri: String, mint_authority_bump: u8) -> Result<()> {
+        validate_metadata(&name, &symbol, &uri)?;
+
+        // The token program reallocates the mint on every field update without paying for it,
+        // so the mint is topped up first for the largest size it goes through
+        let mint_info = self.mint.to_account_info();
+        let mut metadata = {
+            let data = mint_info.try_borrow_data()?;
+            StateWithExtensions::<MintState>::unpack(&data)?.get_variable_len_extension::<TokenMetadata>()?
+        };
+        let base_len = mint_info.data_len() - metadata.tlv_size_of()?;
+        let updates = [(Field::Name, name.clone()), (Field::Symbol, symbol.clone()), (Field::Uri, uri.clone())];
+        let mut max_len = mint_info.data_len();
+        for (field, value) in updates.iter() {
+            metadata.update(field.clone(), value.clone());
+            max_len = max_len.max(base_len + metadata.tlv_size_of()?);
+        }
+        let top_up = Rent::get()?.minimum_balance(max_len).saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            sol_transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    SOLTransfer {
+                        from: self.creator.to_account_info(),
+                        to: mint_info.clone(),
+                    }
+                ),
+                top_up,
+            )?;
+        }
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, mint_pubkey.as_ref(), &[mint_authority_bump]]];
+        for (field, value) in updates {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TokenMetadataUpdateField {
+                        program_id: self.token_program.to_account_info(),
+                        metadata: mint_info.clone(),
+                        update_authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                field,
+                value,
+            )?;
+        }
+
+        emit!(MetadataUpdated {
+            mint: mint_pubkey,
+            creator: self.creator.key(),
+            name,
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Instruction: Update Metadata 2022");
+        msg!("Mint: {}", mint_pubkey);
+
+        Ok(())
+    }
+}
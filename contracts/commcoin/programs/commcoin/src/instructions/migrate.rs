@@ -0,0 +1,116 @@
+use anchor_lang::{prelude::*};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::CommcoinError,
+    math,
+    seeds::{BONDING_CURVE_SEED, CONFIG_SEED},
+    states::{BondingCurveState, Config},
+};
+
+
+
+#[derive(Accounts)]
+pub struct WithdrawForMigration<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Config authority, pays for the migration token account
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ CommcoinError::Unauthorized,
+    )]
+    pub global_config: Account<'info, Config>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub bonding_curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: wallet that seeds the external pool, only receives lamports and tokens
+    #[account(mut)]
+    pub migration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = migration_wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub migration_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: only receives lamports, address is checked against the config
+    #[account(
+        mut,
+        address = global_config.fee_recipient @ CommcoinError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawForMigration<'info>{
+    pub fn withdraw(&mut self, bonding_curve_bump: u8) -> Result<()> {
+        require!(self.bonding_curve.complete, CommcoinError::CurveNotComplete);
+
+        let sol_amount = self.bonding_curve.real_sol_reserves;
+        let fee = Config::calculate_fee(sol_amount, self.global_config.migration_fee_bps)?;
+        let migration_sol = math::sub(sol_amount, fee)?;
+        let token_amount = self.bonding_curve_token_vault.amount;
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[BONDING_CURVE_SEED, mint_pubkey.as_ref(), &[bonding_curve_bump]]];
+
+        if token_amount > 0 {
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.bonding_curve_token_vault.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                        to: self.migration_token_account.to_account_info(),
+                        authority: self.bonding_curve.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                token_amount,
+                self.mint.decimals,
+            )?;
+        }
+
+        // The curve PDA is owned by this program, so lamports are moved directly
+        let curve_info = self.bonding_curve.to_account_info();
+        math::move_lamports(&curve_info, &self.migration_wallet.to_account_info(), migration_sol)?;
+        math::move_lamports(&curve_info, &self.fee_recipient.to_account_info(), fee)?;
+
+        let bonding_curve_state = &mut self.bonding_curve;
+        bonding_curve_state.real_sol_reserves = 0;
+        bonding_curve_state.real_token_reserves = 0;
+        bonding_curve_state.check_solvency(&curve_info)?;
+
+        msg!("Instruction: Withdraw For Migration");
+        msg!("Mint: {}", mint_pubkey);
+        msg!("SOL: {}, fee: {}, tokens: {}", migration_sol, fee, token_amount);
+
+        Ok(())
+    }
+}
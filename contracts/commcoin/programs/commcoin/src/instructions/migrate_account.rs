@@ -0,0 +1,116 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    errors::CommcoinError,
+    program::Commcoin,
+    states::{BondingCurveState, Config, CreatorVesting, Proposal, UserStakeInfo, VaultConfig, VaultMilestones, Versioned, VERSION_OFFSET},
+};
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Upgrade authority of the program, pays rent for any extra space
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ CommcoinError::Unauthorized)]
+    pub program: Program<'info, Commcoin>,
+
+    // Migrations follow program upgrades, so they are gated like the upgrade itself.
+    // The config is one of the accounts migrated, it can't be read to check its authority.
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ CommcoinError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: versioned account of this program, its type is read from the discriminator in the handler
+    #[account(mut, owner = crate::ID @ CommcoinError::InvalidMigrationTarget)]
+    pub target: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+    // Moves one account from layout version N to N + 1, growing it first if the
+    // new layout needs more space. Call again until the account is up to date.
+    // Accounts from the first deployment have no version byte, they are version 0
+    // and move straight to the current layout.
+    pub fn migrate_account(&mut self) -> Result<()> {
+        let discriminator = {
+            let data = self.target.try_borrow_data()?;
+            require!(data.len() > VERSION_OFFSET, CommcoinError::InvalidMigrationTarget);
+            data[..VERSION_OFFSET].to_vec()
+        };
+
+        if discriminator == Config::DISCRIMINATOR {
+            self.upgrade::<Config>(8 + Config::LEN)
+        } else if discriminator == BondingCurveState::DISCRIMINATOR {
+            self.upgrade::<BondingCurveState>(BondingCurveState::LEN)
+        } else if discriminator == VaultConfig::DISCRIMINATOR {
+            self.upgrade::<VaultConfig>(VaultConfig::LEN)
+        } else if discriminator == UserStakeInfo::DISCRIMINATOR {
+            self.upgrade::<UserStakeInfo>(UserStakeInfo::LEN)
+        } else if discriminator == VaultMilestones::DISCRIMINATOR {
+            self.upgrade::<VaultMilestones>(VaultMilestones::LEN)
+        } else if discriminator == Proposal::DISCRIMINATOR {
+            self.upgrade::<Proposal>(Proposal::LEN)
+        } else if discriminator == CreatorVesting::DISCRIMINATOR {
+            self.upgrade::<CreatorVesting>(CreatorVesting::LEN)
+        } else {
+            err!(CommcoinError::InvalidMigrationTarget)
+        }
+    }
+
+    fn upgrade<T>(&mut self, len: usize) -> Result<()>
+    where
+        T: Versioned + AccountSerialize + AccountDeserialize,
+    {
+        let target = self.target.to_account_info();
+        let old_len = target.data_len();
+        // Accounts created before layouts were versioned have no version byte, only their size tells them apart
+        let legacy = old_len != len && T::LEGACY_LENS.contains(&old_len);
+        let version = if legacy { 0 } else { target.try_borrow_data()?[VERSION_OFFSET] };
+        require!(version < T::CURRENT_VERSION, CommcoinError::AccountUpToDate);
+
+        // The legacy layout is read before the account grows, fields shift by the version byte
+        let legacy_account = if legacy {
+            Some(T::from_legacy(&target.try_borrow_data()?[VERSION_OFFSET..], &Clock::get()?)?)
+        } else {
+            None
+        };
+
+        if old_len < len {
+            // Only the rent of the extra space. The balance above the old minimum belongs
+            // to the account, e.g. curve reserves, creator fees or unclaimed staker rewards.
+            let rent = Rent::get()?;
+            let rent_due = rent.minimum_balance(len).saturating_sub(rent.minimum_balance(old_len));
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.authority.to_account_info(),
+                            to: target.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            // New space is zeroed, fields carved out of it read as zero until upgraded
+            target.realloc(len, true)?;
+        }
+
+        let account = match legacy_account {
+            Some(account) => account,
+            None => {
+                let mut account = T::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+                account.upgrade()?;
+                account
+            }
+        };
+        account.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+        msg!("Migrated {} from version {} to {}", target.key(), version, account.version());
+        Ok(())
+    }
+}
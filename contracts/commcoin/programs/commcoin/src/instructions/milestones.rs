@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::CommcoinError,
+    events::MilestoneTokensClaimed,
+    math,
+    seeds::{VAULT_CONFIG_SEED, VAULT_MILESTONES_SEED},
+    states::{MilestoneArgs, VaultConfig, VaultError, VaultMilestones},
+};
+
+#[derive(Accounts)]
+pub struct SetMilestones<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>, // Vault authority, pays rent and deposits the locked allocation
+
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config,
+        constraint = vault.authority == creator.key() @ CommcoinError::Unauthorized,
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = VaultMilestones::LEN,
+        seeds = [VAULT_MILESTONES_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub milestones: Account<'info, VaultMilestones>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = milestones,
+        associated_token::token_program = token_program,
+    )]
+    pub milestone_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+        token::token_program = token_program,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetMilestones<'info> {
+    // One-time setup by the creator. The allocation released by the milestones is
+    // locked up front, what arrives after any transfer fee caps what can be released.
+    // Whatever is still locked at `deadline` can be claimed back, see VaultMilestones::claimable.
+    pub fn set_milestones(&mut self, args: Vec<MilestoneArgs>, deadline: i64, milestones_bump: u8) -> Result<()> {
+        let vault_key = self.vault.key();
+        let now = Clock::get()?.unix_timestamp;
+        self.milestones
+            .initialize(&self.vault, vault_key, &args, self.mint.supply, deadline, now, milestones_bump)?;
+
+        let total_release = self.milestones.total_release()?;
+        if total_release > 0 {
+            transfer_checked(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.creator_token_account.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                        to: self.milestone_token_account.to_account_info(),
+                        authority: self.creator.to_account_info(),
+                    },
+                ),
+                total_release,
+                self.mint.decimals,
+            )?;
+            self.milestone_token_account.reload()?;
+        }
+        self.milestones.locked_tokens = self.milestone_token_account.amount;
+
+        msg!("Instruction: Set Milestones");
+        msg!("Vault: {}, milestones: {}, locked: {}, deadline: {}", vault_key, args.len(), self.milestones.locked_tokens, deadline);
+
+        Ok(())
+    }
+}
+
+// Permissionless, marks milestones passed by stake that didn't pass the milestones account to `stake`
+#[derive(Accounts)]
+pub struct ReachMilestones<'info> {
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_MILESTONES_SEED, vault.key().as_ref()],
+        bump = milestones.bump,
+        has_one = vault,
+    )]
+    pub milestones: Account<'info, VaultMilestones>,
+}
+
+impl<'info> ReachMilestones<'info> {
+    pub fn reach_milestones(&mut self) -> Result<()> {
+        self.milestones.reach(&self.vault, Clock::get()?.unix_timestamp)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimMilestoneTokens<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_MILESTONES_SEED, vault.key().as_ref()],
+        bump = milestones.bump,
+        has_one = vault,
+        has_one = creator @ CommcoinError::Unauthorized,
+    )]
+    pub milestones: Account<'info, VaultMilestones>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = milestones,
+        associated_token::token_program = token_program,
+    )]
+    pub milestone_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+        token::token_program = token_program,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimMilestoneTokens<'info> {
+    // Pays out everything reached milestones have unlocked and the creator hasn't claimed yet,
+    // after the deadline also the release of the milestones that weren't reached
+    pub fn claim_milestone_tokens(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.milestones.claimable(now)?;
+        require!(amount > 0, VaultError::NothingToClaim);
+
+        let vault_key = self.vault.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_MILESTONES_SEED, vault_key.as_ref(), &[self.milestones.bump]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.milestone_token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.creator_token_account.to_account_info(),
+                    authority: self.milestones.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        self.milestones.claimed_tokens = math::add(self.milestones.claimed_tokens, amount)?;
+
+        emit!(MilestoneTokensClaimed {
+            vault: vault_key,
+            creator: self.creator.key(),
+            amount,
+            total_claimed: self.milestones.claimed_tokens,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Claim Milestone Tokens");
+        msg!("Creator: {}, tokens: {}", self.creator.key(), amount);
+
+        Ok(())
+    }
+}
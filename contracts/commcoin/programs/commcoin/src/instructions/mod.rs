@@ -1,20 +1,36 @@
 pub mod configure;
 pub use configure::*;
+pub mod update_config;
+pub use update_config::*;
+pub mod authority;
+pub use authority::*;
 pub mod launch;
 pub use launch::*;
-pub mod extend_account;
-pub use extend_account::*;
-pub mod realloc;
-pub use realloc::*;
-pub mod pantru;
-pub use pantru::*;
-pub mod rope;
-pub use rope::*;
+pub mod launch_2022;
+pub use launch_2022::*;
 pub mod buy;
 pub use buy::*;
 pub mod sell;
 pub use sell::*;
+pub mod stack;
+pub use stack::*;
+pub mod rewards;
+pub use rewards::*;
+pub mod milestones;
+pub use milestones::*;
+pub mod governance;
+pub use governance::*;
+pub mod vesting;
+pub use vesting::*;
+pub mod creator_fees;
+pub use creator_fees::*;
+pub mod metadata;
+pub use metadata::*;
+pub mod oracle;
+pub use oracle::*;
 // pub mod swap;
 // pub use swap::*;
-// pub mod migrate;
-// pub use migrate::*;
\ No newline at end of file
+pub mod migrate;
+pub use migrate::*;
+pub mod migrate_account;
+pub use migrate_account::*;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{seeds::BONDING_CURVE_SEED, states::BondingCurveState};
+
+// Price reading returned to callers, other programs get it through CPI return data.
+// TWAP over a period = (later.price_cumulative - earlier.price_cumulative) / (later.slot - earlier.slot)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PriceObservation {
+    pub slot: u64,
+    pub spot_price: u64,        // lamports per PRICE_SCALE base units
+    pub price_cumulative: u128, // brought up to `slot`
+}
+
+#[derive(Accounts)]
+pub struct ObservePrice<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurveState>,
+}
+
+impl<'info> ObservePrice<'info> {
+    pub fn observe_price(&self) -> Result<PriceObservation> {
+        let slot = Clock::get()?.slot;
+        Ok(PriceObservation {
+            slot,
+            spot_price: self.bonding_curve.spot_price()?,
+            price_cumulative: self.bonding_curve.price_cumulative_at(slot)?,
+        })
+    }
+}
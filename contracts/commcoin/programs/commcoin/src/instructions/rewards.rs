@@ -0,0 +1,73 @@
+use anchor_lang::{prelude::*};
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    math,
+    events::RewardsClaimed,
+    seeds::{USER_STAKE_INFO_SEED, VAULT_CONFIG_SEED},
+    states::{VaultConfig, UserStakeInfo, VaultError},
+};
+
+
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>, // Staker, receives the lamports
+
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut, // Rewards are paid out of the vault's lamports
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [USER_STAKE_INFO_SEED, user.key().as_ref(), vault.key().as_ref()],
+        bump = user_stake_info.bump,
+        constraint = user_stake_info.staker == user.key() @ VaultError::StakeInfoMismatch,
+        constraint = user_stake_info.vault_config == vault.key() @ VaultError::StakeInfoMismatch
+    )]
+    pub user_stake_info: Account<'info, UserStakeInfo>,
+}
+
+impl<'info> ClaimRewards<'info>{
+    pub fn claim_rewards(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // A lock that ran out stops weighing more from here on, what it earned so far is kept
+        self.user_stake_info.expire_lock(&mut self.vault, now)?;
+
+        let acc_reward_per_share = self.vault.acc_reward_per_share;
+        let user_stake_info = &mut self.user_stake_info;
+        user_stake_info.settle_rewards(acc_reward_per_share)?;
+
+        let amount = user_stake_info.pending_rewards;
+        require!(amount > 0, VaultError::NoRewardsToClaim);
+        user_stake_info.pending_rewards = 0;
+        user_stake_info.rewards_claimed = math::add(user_stake_info.rewards_claimed, amount)?;
+
+        // The vault PDA is owned by this program, so lamports are moved directly.
+        // Rewards only ever come on top of its rent-exempt balance.
+        math::move_lamports(&self.vault.to_account_info(), &self.user.to_account_info(), amount)?;
+        self.vault.check_solvency(&self.vault.to_account_info())?;
+
+        emit!(RewardsClaimed {
+            vault: self.vault.key(),
+            mint: self.vault.token_mint,
+            staker: self.user.key(),
+            amount,
+            rewards_claimed: self.user_stake_info.rewards_claimed,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Claim Rewards");
+        msg!("Staker: {}, lamports: {}", self.user.key(), amount);
+
+        Ok(())
+    }
+}
@@ -1,17 +1,24 @@
 use anchor_lang::{prelude::*};
 
 use anchor_spl::{
-    associated_token::{Create as CreateIdempotent, AssociatedToken, get_associated_token_address, create_idempotent},
-    token::{mint_to, set_authority, transfer as token_transfer, MintTo, Mint, Token, TokenAccount, Transfer as TokenTransfer},
+    token_2022::Token2022,
+    token_interface::{
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+            state::Mint as MintState,
+        },
+        transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
 };
 
-use anchor_lang::system_program::{self, transfer as sol_transfer, Transfer as SOLTransfer};
-
-declare_program!(commcoin);
-use commcoin::program::Commcoin;
-
 use crate::{
-    states::{Config, BondingCurveState},
+    errors::CommcoinError,
+    events::Trade,
+    instructions::buy::{check_referrer, split_fee, FeeSplit},
+    math,
+    program::Commcoin,
+    seeds::{BONDING_CURVE_SEED, CONFIG_SEED, VAULT_CONFIG_SEED},
+    states::{BondingCurveState, Config, VaultConfig, PRICE_SCALE},
 };
 
 
@@ -23,11 +30,11 @@ pub struct Sell<'info> {
     pub user: Signer<'info>, // User creating the token, pays rent
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"commcoin_bonding_curve", mint.key().as_ref()], // Seed with mint for uniqueness
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()], // Seed with mint for uniqueness
         bump
     )]
     pub bonding_curve: Account<'info, BondingCurveState>,
@@ -36,46 +43,309 @@ pub struct Sell<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub bonding_curve_token_vault: Account<'info, TokenAccount>,
+    pub bonding_curve_token_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump
     )]
-    pub user_curve_token_vault: Account<'info, TokenAccount>,
+    pub global_config: Account<'info, Config>,
 
+    /// CHECK: only receives lamports, address is checked against the config
+    #[account(
+        mut,
+        address = global_config.fee_recipient @ CommcoinError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    // Community vault of this coin, receives the stakers' share of the fee
+    #[account(
+        mut,
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    /// CHECK: referral wallet, only receives its share of the platform fee
+    #[account(mut)]
+    pub referrer: Option<UncheckedAccount<'info>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub program: Program<'info, Commcoin>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> Sell<'info>{
-    pub fn sell(&mut self, amount: u64, max_sol_cost: u64) -> Result<()> {
-        let token_transfer_context = TokenTransfer {
+    // Exact tokens in, what the user receives after fees is floored by `min_sol_output`
+    pub fn sell(&mut self, amount: u64, min_sol_output: u64) -> Result<()> {
+        require!(!self.bonding_curve.complete, CommcoinError::CurveComplete);
+        self.execute(amount, min_sol_output)
+    }
+
+    // Exact SOL out after fees, the tokens sent, including any Token-2022 transfer fee, are capped by `max_tokens_in`
+    pub fn sell_exact_sol_out(&mut self, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+        require!(!self.bonding_curve.complete, CommcoinError::CurveComplete);
+        let quote = SellQuote::for_sol_out(&self.bonding_curve, &self.global_config, sol_out, max_tokens_in)?;
+        let amount = amount_before_transfer_fee(&self.mint.to_account_info(), quote.token_amount)?;
+        require!(amount <= max_tokens_in, CommcoinError::SlippageExceeded);
+        self.execute(amount, sol_out)
+    }
+
+    fn execute(&mut self, amount: u64, min_sol_output: u64) -> Result<()> {
+
+        let token_transfer_context = TransferChecked {
             to: self.bonding_curve_token_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
             from: self.user_curve_token_vault.to_account_info(),
             authority: self.user.to_account_info()
         };
 
         let toke_cpi_program = self.token_program.to_account_info();
-        let mint_pubkey = self.mint.key();
-        let mint_key_bytes: &[u8] = mint_pubkey.as_ref();
 
         let token_transfer_cpi_context = CpiContext::new(
             toke_cpi_program,
             token_transfer_context
         );
 
-        token_transfer(token_transfer_cpi_context, amount)?;
+        let vault_balance_before = self.bonding_curve_token_vault.amount;
+        transfer_checked(token_transfer_cpi_context, amount, self.mint.decimals)?;
+
+        // Price only what reached the curve, a Token-2022 transfer fee is withheld on the way in
+        self.bonding_curve_token_vault.reload()?;
+        let tokens_received = math::sub(self.bonding_curve_token_vault.amount, vault_balance_before)?;
+
+        let SellQuote { sol_output, fee, creator_fee, .. } =
+            SellQuote::new(&self.bonding_curve, &self.global_config, tokens_received)?;
+        let referrer = check_referrer(&self.referrer, &self.user.key())?;
+        let mut split = split_fee(fee, &self.global_config, &self.vault, referrer.is_some())?;
+        let referrer_info = self.referrer.as_ref().map(|referrer| referrer.to_account_info());
+        // A platform share the fee recipient can't take stays with the user
+        let waived = split.skip_unpayable(&self.fee_recipient.to_account_info(), referrer_info.as_ref(), &Rent::get()?);
+        let fee = math::sub(fee, waived)?;
+        let user_output = math::sub(math::sub(sol_output, fee)?, creator_fee)?;
+        require!(user_output >= min_sol_output, CommcoinError::SlippageExceeded);
+        let FeeSplit { platform_fee, staker_fee, referrer_fee } = split;
+
+        // The curve PDA is owned by this program, so lamports are moved directly.
+        // The creator fee never leaves the curve account, it is accrued until claimed.
+        let curve_info = self.bonding_curve.to_account_info();
+        math::move_lamports(&curve_info, &self.user.to_account_info(), user_output)?;
+        math::move_lamports(&curve_info, &self.fee_recipient.to_account_info(), platform_fee)?;
+        math::move_lamports(&curve_info, &self.vault.to_account_info(), staker_fee)?;
+        if let Some(referrer_info) = &self.referrer {
+            math::move_lamports(&curve_info, &referrer_info.to_account_info(), referrer_fee)?;
+        }
 
-        **self.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= max_sol_cost;
-        **self.user.to_account_info().try_borrow_mut_lamports()? += max_sol_cost;
+        if staker_fee > 0 {
+            self.vault.distribute_rewards(staker_fee)?;
+        }
+
+        self.bonding_curve.apply_sell(tokens_received, sol_output)?;
+        self.bonding_curve.accrue_creator_fee(creator_fee)?;
+        self.bonding_curve.check_invariants(&curve_info, self.bonding_curve_token_vault.amount)?;
+
+        emit!(Trade {
+            mint: self.mint.key(),
+            user: self.user.key(),
+            is_buy: false,
+            sol_amount: sol_output,
+            token_amount: tokens_received,
+            fee,
+            staker_fee,
+            creator_fee,
+            referrer,
+            referrer_fee,
+            virtual_sol_reserves: self.bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: self.bonding_curve.virtual_token_reserves,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Instruction: Sell");
+        msg!("Tokens: {}, SOL output: {}, fee: {}", tokens_received, user_output, fee);
 
         Ok(())
 
     }
-}
\ No newline at end of file
+}
+
+// Price and fees for returning `token_amount` to the curve
+pub(crate) struct SellQuote {
+    pub token_amount: u64,
+    pub sol_output: u64,  // leaves the curve reserves
+    pub fee: u64,         // platform fee, including the stakers' and referrer's shares
+    pub creator_fee: u64, // stays in the curve account for the creator
+}
+
+impl SellQuote {
+    pub fn new(curve: &BondingCurveState, config: &Config, token_amount: u64) -> Result<Self> {
+        let sol_output = curve.get_sell_price(token_amount)?;
+        Ok(Self {
+            token_amount,
+            sol_output,
+            fee: Config::calculate_fee(sol_output, config.sell_fee_bps)?,
+            creator_fee: Config::calculate_fee(sol_output, config.creator_fee_bps)?,
+        })
+    }
+
+    // What the user receives after fees
+    pub fn user_output(&self) -> Result<u64> {
+        math::sub(math::sub(self.sol_output, self.fee)?, self.creator_fee)
+    }
+
+    // Smallest sale of at most `max_tokens` that pays out `sol_out` after fees.
+    // Starts from the spot price, off by the price impact of the sale, then brackets the
+    // answer with real quotes and bisects it, like BuyQuote::for_sol_budget.
+    pub fn for_sol_out(curve: &BondingCurveState, config: &Config, sol_out: u64, max_tokens: u64) -> Result<Self> {
+        require!(sol_out > 0 && max_tokens > 0, CommcoinError::InvalidAmount);
+        // A sale the reserves can't pay is larger than the answer, Self::new reports it at the end
+        let pays = |tokens: u64| -> Result<bool> {
+            Ok(match Self::new(curve, config, tokens) {
+                Ok(quote) => quote.user_output()? >= sol_out,
+                Err(_) => true,
+            })
+        };
+        require!(pays(max_tokens)?, CommcoinError::SlippageExceeded);
+
+        let spot_price = curve.spot_price()?;
+        let estimate = if spot_price == 0 {
+            max_tokens
+        } else {
+            math::to_u64(math::mul_div(sol_out as u128, PRICE_SCALE, spot_price as u128)?)
+                .unwrap_or(max_tokens)
+                .clamp(1, max_tokens)
+        };
+
+        // !pays(low - 1) and pays(high) once bracketed, nothing pays for 0 tokens
+        let (mut low, mut high) = if pays(estimate)? {
+            let (mut high, mut step) = (estimate, 1u64);
+            loop {
+                let next = high.saturating_sub(step);
+                if next == 0 || !pays(next)? {
+                    break (next + 1, high);
+                }
+                high = next;
+                step = step.saturating_mul(2);
+            }
+        } else {
+            let (mut low, mut step) = (estimate, 1u64);
+            loop {
+                let next = low.saturating_add(step).min(max_tokens);
+                if pays(next)? {
+                    break (low + 1, next);
+                }
+                low = next;
+                step = step.saturating_mul(2);
+            }
+        };
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pays(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Self::new(curve, config, low)
+    }
+}
+
+// Amount to send for `amount` to arrive after the mint's Token-2022 transfer fee, `amount` without one
+fn amount_before_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != Token2022::id() {
+        return Ok(amount);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(amount);
+    };
+    transfer_fee_config
+        .get_epoch_fee(Clock::get()?.epoch)
+        .calculate_pre_fee_amount(amount)
+        .ok_or(error!(CommcoinError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::{CurveParams, CurveType};
+
+    const REAL_TOKENS: u64 = 793_100_000_000_000;
+
+    fn config() -> Config {
+        let mut config = Config::try_from_slice(&[0u8; Config::LEN]).unwrap();
+        config.sell_fee_bps = 100;
+        config.creator_fee_bps = 50;
+        config
+    }
+
+    // A curve `sold` tokens into its supply, so sells have reserves to pay from
+    fn curve(curve_type: CurveType, curve_params: CurveParams, sold: u64) -> BondingCurveState {
+        let mut curve = BondingCurveState {
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_token_reserves: REAL_TOKENS,
+            initial_real_token_reserves: REAL_TOKENS,
+            curve_type,
+            curve_params,
+            ..Default::default()
+        };
+        let cost = curve.get_buy_price(sold).unwrap();
+        curve.virtual_token_reserves -= sold;
+        curve.real_token_reserves -= sold;
+        curve.virtual_sol_reserves += cost;
+        curve.real_sol_reserves = cost;
+        curve
+    }
+
+    fn curves() -> [BondingCurveState; 3] {
+        let sold = 300_000_000_000_000;
+        [
+            curve(CurveType::ConstantProduct, CurveParams::default(), sold),
+            curve(CurveType::Linear, CurveParams { start_price: 28, end_price: 400, growth_bps: 0 }, sold),
+            curve(
+                CurveType::Exponential,
+                CurveParams { start_price: 28, end_price: 0, growth_bps: CurveParams::MAX_GROWTH_BPS },
+                sold,
+            ),
+        ]
+    }
+
+    #[test]
+    fn sells_the_fewest_tokens_that_pay_out_the_requested_sol() {
+        let config = config();
+        for curve in curves() {
+            for sol_out in [1, 999, curve.real_sol_reserves / 1_000, curve.real_sol_reserves / 10, curve.real_sol_reserves / 2] {
+                let quote = SellQuote::for_sol_out(&curve, &config, sol_out, u64::MAX).unwrap();
+                assert!(quote.user_output().unwrap() >= sol_out);
+                let fewer = SellQuote::new(&curve, &config, quote.token_amount - 1);
+                assert!(fewer.map_or(true, |fewer| fewer.user_output().unwrap() < sol_out));
+            }
+        }
+    }
+
+    #[test]
+    fn sol_out_is_bounded_by_max_tokens_and_the_reserves() {
+        let config = config();
+        for curve in curves() {
+            let sol_out = curve.real_sol_reserves / 4;
+            let quote = SellQuote::for_sol_out(&curve, &config, sol_out, u64::MAX).unwrap();
+            assert!(SellQuote::for_sol_out(&curve, &config, sol_out, quote.token_amount).is_ok());
+            assert!(SellQuote::for_sol_out(&curve, &config, sol_out, quote.token_amount - 1).is_err());
+
+            assert!(SellQuote::for_sol_out(&curve, &config, curve.real_sol_reserves + 1, u64::MAX).is_err());
+            assert!(SellQuote::for_sol_out(&curve, &config, 0, u64::MAX).is_err());
+        }
+    }
+}
@@ -1,45 +1,42 @@
 use anchor_lang::{prelude::*};
 
 use anchor_spl::{
-    associated_token::{Create as CreateIdempotent, AssociatedToken, get_associated_token_address, create_idempotent},
-    token::{mint_to, set_authority, transfer as token_transfer, MintTo, Mint, Token, TokenAccount, Transfer as TokenTransfer},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use anchor_lang::system_program::{self, transfer as sol_transfer, Transfer as SOLTransfer};
-
-// declare_program!(commcoin);
-// use commcoin::program::Commcoin;
-
 use crate::{
-    states::{Config, BondingCurveState, VaultConfig, UserStakeInfo, VaultGoalReached, VaultError},
+    events::{LockExpired, Staked, Unstaked},
+    math,
+    seeds::{USER_STAKE_INFO_SEED, VAULT_CONFIG_SEED, VAULT_MILESTONES_SEED},
+    states::{LockupTier, VaultConfig, VaultMilestones, UserStakeInfo, VaultGoalReached, VaultError, Versioned},
 };
 
-const USER_STAKE_INFO_SEED: &[u8] = b"user_stake_v1";
 
 
 
 #[derive(Accounts)]
 pub struct Stack<'info> {
-    // Accounts for creating the token & metadata
     #[account(mut)]
-    pub user: Signer<'info>, // User creating the token, pays rent
+    pub user: Signer<'info>, // Staker, pays rent for their stake info on first stake
 
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
-    #[account(mut)] // To update current_tokens_staked and goal_reached
+    #[account(
+        mut, // To update current_tokens_staked, total_weight and goal_reached
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
     pub vault: Account<'info, VaultConfig>,
 
     #[account(
         mut,
         // Vault token account must match the one in vault_config
         constraint = vault_token_account.key() == vault.vault_token_account @ VaultError::InvalidVaultTokenAccount,
-        // Ensure the mint matches
-        constraint = vault_token_account.mint == vault.token_mint @ VaultError::MintMismatch
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
 
-   #[account(
+    #[account(
         init_if_needed, // Create UserStakeInfo if it's their first time staking
         payer = user,
         space = UserStakeInfo::LEN,
@@ -48,67 +45,244 @@ pub struct Stack<'info> {
     )]
     pub user_stake_info: Account<'info, UserStakeInfo>,
 
-
     // User's token account from which they are staking
     #[account(
         mut,
         // Ensure it's for the correct mint
         constraint = user_token_account.mint == vault.token_mint @ VaultError::MintMismatch
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    // Pass when the vault has milestones so the ones this stake passes are reached right away
+    #[account(
+        mut,
+        seeds = [VAULT_MILESTONES_SEED, vault.key().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, VaultMilestones>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> Stack<'info>{
-    pub fn stake(&mut self, amount: u64, user_stack_info_bump: u8) -> Result<()> {
-        
-        let vault_config = &mut self.vault;
-        let user_stake_info = &mut self.user_stake_info;
+    // Stakes `amount_to_stake` and locks the whole position for `lockup`, see UserStakeInfo::apply_lockup
+    pub fn stake(&mut self, amount_to_stake: u64, lockup: LockupTier, user_stake_info_bump: u8) -> Result<()> {
+        require!(amount_to_stake > 0, VaultError::StakeAmountMustBePositive);
 
-        // Transfer tokens from user's ATA to the vault's token account (PDA)
-        let cpi_accounts = TokenTransfer {
+        // Transfer tokens from user's token account to the vault's token account
+        let vault_balance_before = self.vault_token_account.amount;
+        let cpi_accounts = TransferChecked {
             from: self.user_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
             to: self.vault_token_account.to_account_info(),
             authority: self.user.to_account_info(), // Staker signs for their tokens
         };
         let cpi_program = self.token_program.to_account_info();
         let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
-        token_transfer(cpi_context, amount_to_stake)?;
+        transfer_checked(cpi_context, amount_to_stake, self.mint.decimals)?;
+
+        // Credit what actually arrived, a Token-2022 transfer fee is withheld on the way in
+        self.vault_token_account.reload()?;
+        let amount_received = math::sub(self.vault_token_account.amount, vault_balance_before)?;
+        require!(amount_received > 0, VaultError::StakeAmountMustBePositive);
+
+        let vault_config = &mut self.vault;
+        let user_stake_info = &mut self.user_stake_info;
 
-        // Update user's stake info
+        // Update user's stake info, rewards earned so far are settled at the old weight
+        let now = Clock::get()?.unix_timestamp;
+        if user_stake_info.staker == Pubkey::default() {
+            user_stake_info.version = UserStakeInfo::CURRENT_VERSION; // first stake, account was just created
+        }
         user_stake_info.staker = self.user.key();
         user_stake_info.vault_config = vault_config.key();
-        user_stake_info.amount_staked += amount_to_stake; // Accumulate stake
-        user_stake_info.bump = user_stack_bump;
-
+        user_stake_info.expire_lock(vault_config, now)?;
+        user_stake_info.settle_rewards(vault_config.acc_reward_per_share)?;
+        user_stake_info.amount_staked = math::add(user_stake_info.amount_staked, amount_received)?; // Accumulate stake
+        user_stake_info.bump = user_stake_info_bump;
+        user_stake_info.apply_lockup(lockup, now)?;
+        user_stake_info.reweigh(vault_config)?;
+        user_stake_info.reset_reward_debt(vault_config.acc_reward_per_share)?;
 
         // Update vault's total staked amount
-        vault_config.current_tokens_staked += amount_to_stake;
+        vault_config.current_tokens_staked = math::add(vault_config.current_tokens_staked, amount_received)?;
+        vault_config.check_invariants(self.vault_token_account.amount)?;
 
         msg!(
             "User {} staked {} tokens. New total stake for user: {}. Vault total: {}",
             user_stake_info.staker,
-            amount_to_stake,
+            amount_received,
             user_stake_info.amount_staked,
             vault_config.current_tokens_staked
         );
 
-        // Check if goal is reached
-        if !vault_config.goal_reached && vault_config.current_tokens_staked >= vault_config.goal_tokens_to_reach {
+        emit!(Staked {
+            vault: vault_config.key(),
+            mint: vault_config.token_mint,
+            staker: user_stake_info.staker,
+            amount: amount_received,
+            user_total_staked: user_stake_info.amount_staked,
+            vault_total_staked: vault_config.current_tokens_staked,
+            lockup: user_stake_info.lockup,
+            unlock_at: user_stake_info.unlock_at,
+            vault_total_weight: vault_config.total_weight,
+            timestamp: now,
+        });
+
+        // Check if goal is reached, only locked stakes count, at their token amount
+        if !vault_config.goal_reached && vault_config.locked_tokens >= vault_config.goal_tokens_to_reach {
             vault_config.goal_reached = true;
-            msg!("VAULT GOAL REACHED! Current staked: {}, Goal: {}", vault_config.current_tokens_staked, vault_config.goal_tokens_to_reach);
-            // Emit an event or further actions can be triggered here
+            msg!("VAULT GOAL REACHED! Locked tokens: {}, Goal: {}", vault_config.locked_tokens, vault_config.goal_tokens_to_reach);
             emit!(VaultGoalReached {
                 vault_config: vault_config.key(),
-                total_staked_at_goal: vault_config.current_tokens_staked,
+                total_staked_at_goal: vault_config.locked_tokens,
             });
         }
 
+        if let Some(milestones) = self.milestones.as_mut() {
+            milestones.reach(vault_config, now)?;
+        }
+
         Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Unstack<'info> {
+    pub user: Signer<'info>, // Must be the original staker
 
+    #[account(address = vault.token_mint @ VaultError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut, // To update current_tokens_staked
+        seeds = [VAULT_CONFIG_SEED, mint.key().as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account @ VaultError::InvalidVaultTokenAccount,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        // User must have stake info, and it must be for this vault
+        seeds = [USER_STAKE_INFO_SEED, user.key().as_ref(), vault.key().as_ref()],
+        bump = user_stake_info.bump, // Use the stored bump for verification
+        constraint = user_stake_info.staker == user.key() @ VaultError::StakeInfoMismatch,
+        constraint = user_stake_info.vault_config == vault.key() @ VaultError::StakeInfoMismatch
+    )]
+    pub user_stake_info: Account<'info, UserStakeInfo>,
+
+    // User's token account to receive the unstaked tokens
+    #[account(
+        mut,
+        constraint = user_token_account.mint == vault.token_mint @ VaultError::MintMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Unstack<'info>{
+    // See UserStakeInfo::check_unstake for the rules, what stays afterwards is back to 1x
+    pub fn unstake(&mut self, amount_to_unstake: u64) -> Result<()> {
+        self.user_stake_info
+            .check_unstake(amount_to_unstake, Clock::get()?.unix_timestamp)?;
+
+        // The vault PDA owns the vault token account and signs the transfer back
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_CONFIG_SEED, mint_pubkey.as_ref(), &[self.vault.bump_config]]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        transfer_checked(cpi_context, amount_to_unstake, self.mint.decimals)?;
+        self.vault_token_account.reload()?;
+
+        let vault_config = &mut self.vault;
+        let user_stake_info = &mut self.user_stake_info;
+
+        user_stake_info.settle_rewards(vault_config.acc_reward_per_share)?;
+        user_stake_info.amount_staked = math::sub(user_stake_info.amount_staked, amount_to_unstake)?;
+        user_stake_info.lockup = LockupTier::Flexible;
+        user_stake_info.reweigh(vault_config)?;
+        user_stake_info.reset_reward_debt(vault_config.acc_reward_per_share)?;
+        vault_config.current_tokens_staked = math::sub(vault_config.current_tokens_staked, amount_to_unstake)?;
+        vault_config.check_invariants(self.vault_token_account.amount)?;
+
+        msg!(
+            "User {} unstaked {} tokens. Remaining stake for user: {}. Vault total: {}",
+            user_stake_info.staker,
+            amount_to_unstake,
+            user_stake_info.amount_staked,
+            vault_config.current_tokens_staked
+        );
+
+        emit!(Unstaked {
+            vault: vault_config.key(),
+            mint: vault_config.token_mint,
+            staker: user_stake_info.staker,
+            amount: amount_to_unstake,
+            user_total_staked: user_stake_info.amount_staked,
+            vault_total_staked: vault_config.current_tokens_staked,
+            vault_total_weight: vault_config.total_weight,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+// Permissionless, drops a position whose lock has run out back to 1x.
+// Positions also decay on their staker's next stake, unstake or claim,
+// this keeps the vault weight honest for the ones left alone.
+#[derive(Accounts)]
+pub struct ExpireLock<'info> {
+    #[account(
+        mut, // To update total_weight
+        seeds = [VAULT_CONFIG_SEED, vault.token_mint.as_ref()],
+        bump = vault.bump_config
+    )]
+    pub vault: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [USER_STAKE_INFO_SEED, user_stake_info.staker.as_ref(), vault.key().as_ref()],
+        bump = user_stake_info.bump,
+        constraint = user_stake_info.vault_config == vault.key() @ VaultError::StakeInfoMismatch
+    )]
+    pub user_stake_info: Account<'info, UserStakeInfo>,
+}
+
+impl<'info> ExpireLock<'info> {
+    pub fn expire_lock(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.user_stake_info.expire_lock(&mut self.vault, now)?,
+            VaultError::LockNotExpired
+        );
+
+        emit!(LockExpired {
+            vault: self.vault.key(),
+            staker: self.user_stake_info.staker,
+            weighted_stake: self.user_stake_info.weighted_stake,
+            vault_total_weight: self.vault.total_weight,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Expire Lock");
+        msg!("Staker: {}, weight: {}", self.user_stake_info.staker, self.user_stake_info.weighted_stake);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CommcoinError, seeds::CONFIG_SEED, states::Config};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ CommcoinError::Unauthorized,
+    )]
+    global_config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn process(&mut self, new_config: Config) -> Result<()> {
+        new_config.validate()?;
+
+        // authority fields are only changed through the two-step handover,
+        // the layout version and padding only through migrate_account
+        let current = &self.global_config;
+        self.global_config.set_inner(Config {
+            version: current.version,
+            authority: current.authority,
+            pending_authority: current.pending_authority,
+            _reserved: current._reserved,
+            ..new_config
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::CommcoinError,
+    events::VestedTokensClaimed,
+    math,
+    seeds::CREATOR_VESTING_SEED,
+    states::CreatorVesting,
+};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub creator: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_VESTING_SEED, mint.key().as_ref()],
+        bump = creator_vesting.bump,
+        has_one = mint,
+        has_one = creator @ CommcoinError::Unauthorized,
+    )]
+    pub creator_vesting: Account<'info, CreatorVesting>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator_vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+        token::token_program = token_program,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimVested<'info> {
+    // Pays out everything vested so far that the creator hasn't claimed yet
+    pub fn claim_vested(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.creator_vesting.claimable(now)?;
+        require!(amount > 0, CommcoinError::NothingVested);
+
+        let mint_pubkey = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[CREATOR_VESTING_SEED, mint_pubkey.as_ref(), &[self.creator_vesting.bump]]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vesting_token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.creator_token_account.to_account_info(),
+                    authority: self.creator_vesting.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        let vesting = &mut self.creator_vesting;
+        vesting.claimed_amount = math::add(vesting.claimed_amount, amount)?;
+
+        emit!(VestedTokensClaimed {
+            mint: mint_pubkey,
+            creator: self.creator.key(),
+            amount,
+            total_claimed: vesting.claimed_amount,
+            timestamp: now,
+        });
+
+        msg!("Instruction: Claim Vested");
+        msg!("Creator: {}, tokens: {}", self.creator.key(), amount);
+
+        Ok(())
+    }
+}
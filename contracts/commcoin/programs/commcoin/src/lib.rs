@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
+pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod math;
+pub mod merkle;
+pub mod seeds;
 pub mod states;
 
 use crate::instructions::*;
@@ -15,70 +20,177 @@ pub mod commcoin {
         ctx.accounts.process(new_config)
     }
 
+    //  called by the config authority to change fees and curve parameters
+    pub fn update_config(ctx: Context<UpdateConfig>, new_config: states::Config) -> Result<()> {
+        ctx.accounts.process(new_config)
+    }
+
+    pub fn nominate_authority(ctx: Context<NominateAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.process(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
    
     //  called by a creator to launch a token on the platform
-    pub fn extend_account<'info>(
-        ctx: Context<'_, '_, '_, 'info, ExtendAccount<'info>>,
+    pub fn create_coin<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCoinInfo<'info>>, args: CreateCoinArgs
     ) -> Result<()> {
         ctx.accounts
-            .extend_account()
+            .create_coin(
+                args,
+                ctx.bumps.mint_authority,
+                ctx.bumps.vault,
+                ctx.bumps.vault_token_account,
+                ctx.bumps.bonding_curve,
+                ctx.bumps.creator_vesting,
+                ctx.bumps.creator_buyer_record,
+            )
     }
 
-    //  called by a creator to launch a token on the platform
-    pub fn alloc_account<'info>(
-        ctx: Context<'_, '_, '_, 'info, Realloc<'info>>,
+    //  same as create_coin, but the mint is a Token-2022 mint carrying its own metadata
+    //  and an optional transfer fee paid to the creator
+    pub fn create_coin_2022<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCoin2022Info<'info>>, args: CreateCoinArgs, transfer_fee: Option<TransferFeeArgs>
     ) -> Result<()> {
         ctx.accounts
-            .realloc_account()
+            .create_coin_2022(
+                args,
+                transfer_fee,
+                ctx.bumps.mint_authority,
+                ctx.bumps.vault,
+                ctx.bumps.bonding_curve,
+                ctx.bumps.creator_vesting,
+                ctx.bumps.creator_buyer_record,
+            )
     }
 
-    //  called by a creator to launch a token on the platform
-    pub fn paint_account<'info>(
-        ctx: Context<'_, '_, '_, 'info, Pantru<'info>>,
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>, amount: u64, max_sol_cost: u64, proof: Vec<[u8; 32]>
     ) -> Result<()> {
         ctx.accounts
-            .paint_account()
+            .buy(amount, max_sol_cost, &proof, ctx.bumps.bonding_curve, ctx.bumps.buyer_record)
     }
 
-    pub fn rope_account<'info>(
-        ctx: Context<'_, '_, '_, 'info, Rope<'info>>,
+    //  buy with a fixed SOL amount, fees included, and a minimum number of tokens out
+    pub fn buy_exact_sol_in<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>, sol_in: u64, min_tokens_out: u64, proof: Vec<[u8; 32]>
     ) -> Result<()> {
         ctx.accounts
-            .rope_now()
+            .buy_exact_sol_in(sol_in, min_tokens_out, &proof, ctx.bumps.bonding_curve, ctx.bumps.buyer_record)
     }
 
-
-     //  called by a creator to launch a token on the platform
-    pub fn create_coin<'info>(
-        ctx: Context<'_, '_, '_, 'info, CreateCoinInfo<'info>>,
+    //  sell a fixed number of tokens for at least `min_sol_output` after fees.
+    //  Exact tokens in, the counterpart of buy_exact_sol_in.
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>, amount: u64, min_sol_output: u64
     ) -> Result<()> {
-        // msg!("New Mint: {}", ctx.cpi);
-        // msg!("New Mint: {}", ctx.accounts.extend_account.ExtendAccount);
-        // crate::cpi::configure({});
-
         ctx.accounts
-            .create_coin(ctx.bumps.mint_authority, ctx.bumps.vault, ctx.bumps.vault_token_account)
+            .sell(amount, min_sol_output)
     }
 
-    pub fn buy<'info>(
-        ctx: Context<'_, '_, '_, 'info, Buy<'info>>, amount: u64, max_sol_cost: u64
+    //  sell for a fixed SOL amount after fees, spending at most `max_tokens_in`.
+    //  Exact SOL out, the counterpart of buy.
+    pub fn sell_exact_sol_out<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>, sol_out: u64, max_tokens_in: u64
     ) -> Result<()> {
-        // msg!("New Mint: {}", ctx.cpi);
-        // msg!("New Mint: {}", ctx.accounts.extend_account.ExtendAccount);
-        // crate::cpi::configure({});
+        ctx.accounts
+            .sell_exact_sol_out(sol_out, max_tokens_in)
+    }
 
+    //  stake coin tokens into the community vault created by create_coin
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Stack<'info>>, amount: u64, lockup: states::LockupTier
+    ) -> Result<()> {
         ctx.accounts
-            .buy(amount, max_sol_cost, ctx.bumps.bonding_curve)
+            .stake(amount, lockup, ctx.bumps.user_stake_info)
     }
 
-     pub fn sell<'info>(
-        ctx: Context<'_, '_, '_, 'info, Sell<'info>>, amount: u64, max_sol_cost: u64
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstack<'info>>, amount: u64
     ) -> Result<()> {
-        // msg!("New Mint: {}", ctx.cpi);
-        // msg!("New Mint: {}", ctx.accounts.extend_account.ExtendAccount);
-        // crate::cpi::configure({});
+        ctx.accounts
+            .unstake(amount)
+    }
+
+    //  anyone can drop a stake whose lock has run out back to 1x
+    pub fn expire_lock(ctx: Context<ExpireLock>) -> Result<()> {
+        ctx.accounts.expire_lock()
+    }
 
+    //  pays out the staker's share of trading fees collected by the vault
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        ctx.accounts.claim_rewards()
+    }
+
+    //  creator sets the vault's staking milestones once and locks the allocation they release,
+    //  milestones not reached by the deadline release nothing and the creator claims their tokens back
+    pub fn set_milestones(ctx: Context<SetMilestones>, milestones: Vec<states::MilestoneArgs>, deadline: i64) -> Result<()> {
+        ctx.accounts.set_milestones(milestones, deadline, ctx.bumps.milestones)
+    }
+
+    //  permissionless, reaches milestones the vault's locked stake has passed
+    pub fn reach_milestones(ctx: Context<ReachMilestones>) -> Result<()> {
+        ctx.accounts.reach_milestones()
+    }
+
+    //  pays the creator the tokens unlocked by reached milestones, and the rest after the deadline
+    pub fn claim_milestone_tokens(ctx: Context<ClaimMilestoneTokens>) -> Result<()> {
+        ctx.accounts.claim_milestone_tokens()
+    }
+
+    //  staker opens a yes/no proposal on their vault, weights are snapshotted at creation
+    pub fn create_proposal(ctx: Context<CreateProposal>, title: String, uri: String, voting_period: i64) -> Result<()> {
+        ctx.accounts.create_proposal(title, uri, voting_period, ctx.bumps.proposal)
+    }
+
+    //  one vote per staker and proposal, weighted by their stake as of the snapshot
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        ctx.accounts.cast_vote(support, ctx.bumps.vote_record)
+    }
+
+    //  permissionless, settles a proposal once voting has ended
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        ctx.accounts.finalize_proposal()
+    }
+
+    //  pays the creator their launch allocation as it vests
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.claim_vested()
+    }
+
+    //  pays out creator fees accrued on the coin's curve, creator only
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        ctx.accounts.claim_creator_fees()
+    }
+
+    //  changes the name, symbol and uri of a classic SPL coin, creator only
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+        ctx.accounts.update_metadata(name, symbol, uri, ctx.bumps.mint_authority)
+    }
+
+    //  same for a Token-2022 coin, the metadata lives on the mint
+    pub fn update_metadata_2022(ctx: Context<UpdateMetadata2022>, name: String, symbol: String, uri: String) -> Result<()> {
+        ctx.accounts.update_metadata(name, symbol, uri, ctx.bumps.mint_authority)
+    }
+
+    //  read-only, current spot price and cumulative price of a coin for TWAP readers
+    pub fn observe_price(ctx: Context<ObservePrice>) -> Result<PriceObservation> {
+        ctx.accounts.observe_price()
+    }
+
+    //  called by the config authority once a curve is complete to seed liquidity elsewhere
+    pub fn withdraw_for_migration<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawForMigration<'info>>,
+    ) -> Result<()> {
         ctx.accounts
-            .sell(amount, max_sol_cost)
+            .withdraw(ctx.bumps.bonding_curve)
+    }
+
+    //  called by the upgrade authority to move a versioned account to the next layout version
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate_account()
     }
 }
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CommcoinError;
+
+// Checked integer math shared by the curves, fees, rewards and lamport moves.
+// Products are taken in u128 and narrowed back with `to_u64`, nothing saturates or wraps.
+
+pub fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn add_i64(a: i64, b: i64) -> Result<i64> {
+    a.checked_add(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn add_u128(a: u128, b: u128) -> Result<u128> {
+    a.checked_add(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn sub_u128(a: u128, b: u128) -> Result<u128> {
+    a.checked_sub(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn mul(a: u128, b: u128) -> Result<u128> {
+    a.checked_mul(b).ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn div(numerator: u128, denominator: u128) -> Result<u128> {
+    numerator
+        .checked_div(denominator)
+        .ok_or(error!(CommcoinError::MathOverflow))
+}
+
+pub fn div_ceil(numerator: u128, denominator: u128) -> Result<u128> {
+    require!(denominator > 0, CommcoinError::MathOverflow);
+    Ok(numerator.div_ceil(denominator))
+}
+
+// a * b / denominator, rounded down
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128> {
+    div(mul(a, b)?, denominator)
+}
+
+// a * b / denominator, rounded up
+pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Result<u128> {
+    div_ceil(mul(a, b)?, denominator)
+}
+
+// Integer square root, rounded down
+pub fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting from a power of two at or above the root
+    let mut x = 1u128 << (128 - value.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (x + value / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(CommcoinError::MathOverflow))
+}
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+// `bps` basis points of `amount`, rounded down
+pub fn bps(amount: u64, bps: u16) -> Result<u64> {
+    to_u64(mul_div(amount as u128, bps as u128, BPS_DENOMINATOR)?)
+}
+
+// Moves lamports out of an account owned by this program, no CPI needed.
+// The source always keeps its rent-exempt minimum, so a payout can never close it.
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let from_lamports = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(CommcoinError::InsufficientReserves)?;
+    require!(
+        from_lamports >= Rent::get()?.minimum_balance(from.data_len()),
+        CommcoinError::RentExemptionViolated
+    );
+    let to_lamports = add(to.lamports(), amount)?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
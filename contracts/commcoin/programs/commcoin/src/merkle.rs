@@ -0,0 +1,96 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+// Allow-list proofs for scheduled launches, see `BondingCurveState::allow_list_root`.
+// Leaves and nodes are domain separated so a node can never be passed off as a leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+// Pairs are hashed in sorted order, so proofs don't need left/right flags
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[NODE_PREFIX, &node, sibling]).to_bytes()
+        } else {
+            hashv(&[NODE_PREFIX, sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, &a, &b]).to_bytes()
+    }
+
+    // Builds the tree the server publishes: sorted pairs, odd nodes promoted unhashed.
+    // Returns the root and the proof of every leaf.
+    fn tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let mut proofs = vec![Vec::new(); leaves.len()];
+        // Index of each leaf's ancestor on the current level
+        let mut positions: Vec<usize> = (0..leaves.len()).collect();
+        let mut level = leaves.to_vec();
+        while level.len() > 1 {
+            for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+                let sibling = *position ^ 1;
+                if sibling < level.len() {
+                    proof.push(level[sibling]);
+                }
+                *position /= 2;
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| if pair.len() == 2 { node(pair[0], pair[1]) } else { pair[0] })
+                .collect();
+        }
+        (level[0], proofs)
+    }
+
+    fn wallets(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn accepts_every_wallet_of_odd_sized_trees() {
+        for count in [1, 3, 5, 7, 11] {
+            let wallets = wallets(count);
+            let leaves: Vec<_> = wallets.iter().map(leaf).collect();
+            let (root, proofs) = tree(&leaves);
+            for (wallet, proof) in wallets.iter().zip(&proofs) {
+                assert!(verify(proof, &root, leaf(wallet)), "{} leaves", count);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_wallets_off_the_list_and_wrong_proofs() {
+        let wallets = wallets(5);
+        let leaves: Vec<_> = wallets.iter().map(leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        assert!(!verify(&proofs[0], &root, leaf(&Pubkey::new_unique())));
+        assert!(!verify(&proofs[1], &root, leaf(&wallets[0])));
+        assert!(!verify(&[], &root, leaf(&wallets[0])));
+    }
+
+    #[test]
+    fn rejects_an_inner_node_passed_as_a_leaf() {
+        let wallets = wallets(4);
+        let leaves: Vec<_> = wallets.iter().map(leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        // The node over the first pair verifies with the rest of the first proof,
+        // but only as a raw hash. No wallet hashes to it, since leaves are hashed with another prefix.
+        let inner = node(leaves[0], leaves[1]);
+        assert!(verify(&proofs[0][1..], &root, inner));
+        let disguised = Pubkey::new_from_array(inner);
+        assert!(!verify(&proofs[0][1..], &root, leaf(&disguised)));
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+// PDA seed registry. Every PDA of the program is derived from one of these prefixes,
+// exported through the IDL constants so clients derive addresses from the same values.
+// New account types are namespaced with a _v1 suffix. Curves keep the first deployment's
+// prefix, their addresses are live.
+//
+//  config          [CONFIG_SEED]
+//  mint authority  [MINT_AUTHORITY_SEED, mint]         per coin, signs the supply mint then is revoked
+//  bonding curve   [BONDING_CURVE_SEED, mint]
+//  vault config    [VAULT_CONFIG_SEED, mint]
+//  vault tokens    [VAULT_TOKEN_SEED, mint]            classic SPL coins only, Token-2022 uses the vault ATA
+//  stake info      [USER_STAKE_INFO_SEED, user, vault]
+//  buyer record    [BUYER_RECORD_SEED, bonding_curve, user]
+//  milestones      [VAULT_MILESTONES_SEED, vault]     also owns the ATA holding the creator-locked allocation
+//  proposal        [PROPOSAL_SEED, vault, id]          id as u64 little endian, from VaultConfig::proposal_count
+//  vote record     [VOTE_RECORD_SEED, proposal, voter]
+//  creator vesting [CREATOR_VESTING_SEED, mint]        also owns the ATA holding the vesting allocation
+
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config_v1";
+#[constant]
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority_v1";
+#[constant]
+pub const BONDING_CURVE_SEED: &[u8] = b"commcoin_bonding_curve";
+#[constant]
+pub const VAULT_CONFIG_SEED: &[u8] = b"vault_config_v1";
+#[constant]
+pub const VAULT_TOKEN_SEED: &[u8] = b"vault_tokens_v1";
+#[constant]
+pub const USER_STAKE_INFO_SEED: &[u8] = b"user_stake_v1";
+#[constant]
+pub const BUYER_RECORD_SEED: &[u8] = b"buyer_record_v1";
+#[constant]
+pub const VAULT_MILESTONES_SEED: &[u8] = b"vault_milestones_v1";
+#[constant]
+pub const PROPOSAL_SEED: &[u8] = b"proposal_v1";
+#[constant]
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_record_v1";
+#[constant]
+pub const CREATOR_VESTING_SEED: &[u8] = b"creator_vesting_v1";
@@ -1,20 +1,365 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    errors::CommcoinError,
+    math,
+    merkle,
+    states::{
+        AntiSnipeParams, Config, CurveParams, CurveType, LaunchParams, Versioned, RESERVED_LEN,
+        RESERVED_WORDS,
+    },
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AllowList {
+    pub merkle_root: [u8; 32], // see BondingCurveState::allow_list_root for the proof format
+    pub ends_at: i64,          // unix timestamp
+}
+
 #[account]
 #[derive(Default)]
 pub struct BondingCurveState {
+    pub version: u8, // Layout version, see states::version
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
-    pub token_total_supply: u64,
+    pub token_total_supply: u64, // Whole mint supply, including any creator vesting allocation
     pub bump: u8,
-    // Add curve parameters (e.g., curve type identifier, constants)
+    pub complete: bool, // Set once real_sol_reserves reaches the config curve_limit, trading stops
+    pub creator: Pubkey,            // Launched the coin, only one who can claim creator fees
+    pub creator_fees_accrued: u64,  // Claimable lamports held by this account on top of real_sol_reserves
+    pub creator_fees_claimed: u64,  // Lamports paid out to the creator so far
+    pub curve_type: CurveType,      // Pricing used by buy and sell, chosen at launch
+    pub curve_params: CurveParams,  // Copied from the config at launch
+    pub initial_real_token_reserves: u64, // Tokens sold = initial - current real token reserves
+    pub launch_slot: u64,           // Slot of create_coin, or of the first buy once a scheduled launch opens (0 until then)
+    pub anti_snipe: AntiSnipeParams, // Per-wallet cap and cooldown right after launch
+    pub trading_starts_at: i64,     // Unix timestamp, buys fail before it. 0 = open at launch
+    /// Merkle root of the wallets allowed to buy before `allow_list_ends_at`, all zero when unused.
+    ///
+    /// Proof format, as published by the server:
+    ///  - leaf = sha256(0x00 || wallet pubkey bytes)
+    ///  - node = sha256(0x01 || a || b), where a <= b bytewise (pairs are sorted, no left/right flags)
+    ///  - the proof is the list of sibling hashes from the leaf up to the root,
+    ///    passed to `buy` as `Vec<[u8; 32]>`. Odd nodes are promoted to the next level unhashed.
+    pub allow_list_root: [u8; 32],
+    pub allow_list_ends_at: i64,    // Unix timestamp, trading is open to everyone from then on
+    /// Running sum of the spot price (lamports per PRICE_SCALE base units) times the slots it was quoted for.
+    /// The TWAP between two observations is the difference of the sums divided by the slots between them,
+    /// see `observe_price` for an up-to-date reading.
+    pub price_cumulative: u128,
+    pub price_last_slot: u64,       // Slot price_cumulative was last brought up to
+    pub _reserved: [u64; RESERVED_WORDS - 3], // Zeroed, taken by future fields
     // Add other fields as necessary (e.g., is_active flag)
 }
 
 impl BondingCurveState {
     // Calculate space needed for the account
-    // 8 (discriminator) + 32 (mint) + 32 (creator) + 8*4 (reserves) + 1 (bump) + padding/future fields
-    pub const LEN: usize = 8 + (8 * 5) + 1; // Add padding for future use
-}
\ No newline at end of file
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + (8 * 5) + 1 + 1 + 32 + (8 * 2) + 1 + CurveParams::LEN + 8 + 8 + (8 * 3) + 8 + 32 + 8 + 16 + 8 + (RESERVED_LEN - 24);
+
+    // Starting reserves for a new coin, from the launch parameters.
+    // The share of supply sold through the curve follows the global config, taken
+    // from what is left after the creator allocation.
+    pub fn initialize(
+        &mut self,
+        config: &Config,
+        params: &LaunchParams,
+        creator: Pubkey,
+        curve_type: CurveType,
+        anti_snipe: AntiSnipeParams,
+        creator_allocation: u64,
+    ) -> Result<()> {
+        let curve_supply = math::sub(params.token_total_supply, creator_allocation)?;
+        let real_token_reserves = config.real_token_reserves_for(curve_supply)?;
+        require!(
+            real_token_reserves > 0 && params.virtual_token_reserves >= real_token_reserves,
+            CommcoinError::InvalidLaunchParams
+        );
+
+        self.version = Self::CURRENT_VERSION;
+        self.token_total_supply = params.token_total_supply;
+        self.real_token_reserves = real_token_reserves;
+        self.virtual_token_reserves = params.virtual_token_reserves;
+        self.virtual_sol_reserves = params.virtual_sol_reserves;
+        self.real_sol_reserves = 0;
+        self.complete = false;
+        self.creator = creator;
+        self.creator_fees_accrued = 0;
+        self.creator_fees_claimed = 0;
+        self.curve_type = curve_type;
+        self.curve_params = config.curve_params(curve_type);
+        self.initial_real_token_reserves = real_token_reserves;
+        self.launch_slot = Clock::get()?.slot;
+        self.anti_snipe = anti_snipe;
+        self.trading_starts_at = 0;
+        self.allow_list_root = [0u8; 32];
+        self.allow_list_ends_at = 0;
+        self.price_cumulative = 0;
+        self.price_last_slot = self.launch_slot;
+        Ok(())
+    }
+
+    // Optional launch time and allow-list, set right after `initialize`
+    pub fn set_schedule(&mut self, trading_starts_at: Option<i64>, allow_list: Option<AllowList>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(starts_at) = trading_starts_at {
+            require!(starts_at > now, CommcoinError::InvalidLaunchSchedule);
+            self.trading_starts_at = starts_at;
+            // The anti-sniping window starts when trading actually opens
+            self.launch_slot = 0;
+        }
+        if let Some(allow_list) = allow_list {
+            require!(
+                allow_list.merkle_root != [0u8; 32]
+                    && allow_list.ends_at > now.max(self.trading_starts_at),
+                CommcoinError::InvalidLaunchSchedule
+            );
+            self.allow_list_root = allow_list.merkle_root;
+            self.allow_list_ends_at = allow_list.ends_at;
+        }
+        Ok(())
+    }
+
+    // Fails before the scheduled start, and for wallets off the allow-list until it ends
+    pub fn check_trading_open(&mut self, wallet: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp >= self.trading_starts_at, CommcoinError::TradingNotStarted);
+        if self.launch_slot == 0 {
+            self.launch_slot = clock.slot;
+        }
+
+        if self.allow_list_root != [0u8; 32] && clock.unix_timestamp < self.allow_list_ends_at {
+            require!(
+                merkle::verify(proof, &self.allow_list_root, merkle::leaf(wallet)),
+                CommcoinError::NotOnAllowList
+            );
+        }
+        Ok(())
+    }
+
+    pub fn in_protected_window(&self, slot: u64) -> bool {
+        slot < self.launch_slot.saturating_add(self.anti_snipe.protected_slots)
+    }
+
+    pub fn tokens_sold(&self) -> Result<u64> {
+        math::sub(self.initial_real_token_reserves, self.real_token_reserves)
+    }
+
+    pub fn accrue_creator_fee(&mut self, amount: u64) -> Result<()> {
+        self.creator_fees_accrued = math::add(self.creator_fees_accrued, amount)?;
+        Ok(())
+    }
+
+    // Current price of one PRICE_SCALE base units, in lamports
+    pub fn spot_price(&self) -> Result<u64> {
+        self.curve_type.pricing().spot_price(self)
+    }
+
+    // price_cumulative as of `slot`, the price hasn't moved since the last trade
+    pub fn price_cumulative_at(&self, slot: u64) -> Result<u128> {
+        let elapsed = slot.saturating_sub(self.price_last_slot);
+        math::add_u128(
+            self.price_cumulative,
+            math::mul(self.spot_price()? as u128, elapsed as u128)?,
+        )
+    }
+
+    // Credits the pre-trade price for the slots since the last update, run before reserves move
+    fn accumulate_price(&mut self) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        self.price_cumulative = self.price_cumulative_at(slot)?;
+        self.price_last_slot = slot;
+        Ok(())
+    }
+
+    // Product of the virtual reserves, only meaningful for the constant product curve
+    fn product(&self) -> Result<u128> {
+        math::mul(self.virtual_sol_reserves as u128, self.virtual_token_reserves as u128)
+    }
+
+    // Constant product trades round in favour of the curve, so k can only grow
+    fn check_product(&self, product_before: u128) -> Result<()> {
+        if self.curve_type == CurveType::ConstantProduct {
+            require!(self.product()? >= product_before, CommcoinError::InvariantViolated);
+        }
+        Ok(())
+    }
+
+    // Run after every change to the curve state or its balances:
+    //  - the reserves stay within what the curve started with
+    //  - the curve account holds its rent, the SOL reserves and the unclaimed creator fees
+    //  - the token vault holds at least the tokens still for sale
+    pub fn check_invariants(&self, curve_info: &AccountInfo, token_vault_balance: u64) -> Result<()> {
+        require!(
+            token_vault_balance >= self.real_token_reserves,
+            CommcoinError::InvariantViolated
+        );
+        self.check_solvency(curve_info)
+    }
+
+    // Same as `check_invariants` for instructions that don't touch the token vault
+    pub fn check_solvency(&self, curve_info: &AccountInfo) -> Result<()> {
+        require!(
+            self.real_token_reserves <= self.initial_real_token_reserves
+                && self.real_token_reserves <= self.virtual_token_reserves
+                && self.real_sol_reserves <= self.virtual_sol_reserves,
+            CommcoinError::InvariantViolated
+        );
+        let required = math::add(
+            Rent::get()?.minimum_balance(curve_info.data_len()),
+            math::add(self.real_sol_reserves, self.creator_fees_accrued)?,
+        )?;
+        require!(curve_info.lamports() >= required, CommcoinError::InvariantViolated);
+        Ok(())
+    }
+
+    // Lamports needed to take `token_amount` out of the curve, rounded up in favour of the curve.
+    pub fn get_buy_price(&self, token_amount: u64) -> Result<u64> {
+        require!(token_amount > 0, CommcoinError::InvalidAmount);
+        require!(
+            token_amount <= self.real_token_reserves,
+            CommcoinError::InsufficientReserves
+        );
+        self.curve_type.pricing().buy_cost(self, token_amount)
+    }
+
+    // Lamports paid out for returning `token_amount` to the curve, rounded down in favour of the curve.
+    pub fn get_sell_price(&self, token_amount: u64) -> Result<u64> {
+        require!(token_amount > 0, CommcoinError::InvalidAmount);
+
+        let sol_output = self.curve_type.pricing().sell_output(self, token_amount)?;
+        require!(sol_output <= self.real_sol_reserves, CommcoinError::InsufficientReserves);
+        Ok(sol_output)
+    }
+
+    pub fn apply_buy(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
+        self.accumulate_price()?;
+        let product_before = self.product()?;
+        self.virtual_token_reserves = self
+            .virtual_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(CommcoinError::InsufficientReserves)?;
+        self.real_token_reserves = self
+            .real_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(CommcoinError::InsufficientReserves)?;
+        self.virtual_sol_reserves = math::add(self.virtual_sol_reserves, sol_amount)?;
+        self.real_sol_reserves = math::add(self.real_sol_reserves, sol_amount)?;
+        self.check_product(product_before)
+    }
+
+    pub fn apply_sell(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
+        self.accumulate_price()?;
+        let product_before = self.product()?;
+        self.virtual_token_reserves = math::add(self.virtual_token_reserves, token_amount)?;
+        self.real_token_reserves = math::add(self.real_token_reserves, token_amount)?;
+        self.virtual_sol_reserves = self
+            .virtual_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(CommcoinError::InsufficientReserves)?;
+        self.real_sol_reserves = self
+            .real_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(CommcoinError::InsufficientReserves)?;
+        self.check_product(product_before)
+    }
+}
+
+// Layout of the curves launched by the first deployment, read once by migrate_account.
+// They were created at 8 + 41 bytes, or grown to 256 by the old extend instructions.
+#[derive(AnchorDeserialize)]
+struct LegacyBondingCurveState {
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_sol_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    bump: u8,
+}
+
+impl Versioned for BondingCurveState {
+    const CURRENT_VERSION: u8 = 1;
+
+    const LEGACY_LENS: &'static [usize] = &[8 + (8 * 5) + 1, 256];
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    // Constant product curves, the only kind there was, open to everyone with no launch window.
+    // Their creator wasn't recorded, so they accrue no creator fees, and the tokens sold
+    // and the price accumulator count from the migration.
+    fn from_legacy(data: &[u8], clock: &Clock) -> Result<Self> {
+        let legacy = LegacyBondingCurveState::deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(Self {
+            version: Self::CURRENT_VERSION,
+            virtual_sol_reserves: legacy.virtual_sol_reserves,
+            virtual_token_reserves: legacy.virtual_token_reserves,
+            real_sol_reserves: legacy.real_sol_reserves,
+            real_token_reserves: legacy.real_token_reserves,
+            token_total_supply: legacy.token_total_supply,
+            bump: legacy.bump,
+            curve_type: CurveType::ConstantProduct,
+            initial_real_token_reserves: legacy.real_token_reserves,
+            launch_slot: clock.slot,
+            price_last_slot: clock.slot,
+            ..Default::default()
+        })
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // First deployment curve: five u64 reserves and the bump
+    fn legacy_curve_data() -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [30_000_000_000u64, 1_073_000_000_000_000, 2_000_000_000, 793_100_000_000_000, 1_000_000_000_000_000] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(253);
+        data
+    }
+
+    fn assert_migrated(curve: &BondingCurveState) {
+        assert_eq!(curve.version, BondingCurveState::CURRENT_VERSION);
+        assert_eq!(curve.virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(curve.virtual_token_reserves, 1_073_000_000_000_000);
+        assert_eq!(curve.real_sol_reserves, 2_000_000_000);
+        assert_eq!(curve.real_token_reserves, 793_100_000_000_000);
+        assert_eq!(curve.token_total_supply, 1_000_000_000_000_000);
+        assert_eq!(curve.bump, 253);
+        assert_eq!(curve.curve_type, CurveType::ConstantProduct);
+        assert!(!curve.complete);
+        assert_eq!(curve.creator, Pubkey::default());
+        assert_eq!(curve.initial_real_token_reserves, curve.real_token_reserves);
+        assert_eq!(curve.price_last_slot, 42);
+    }
+
+    #[test]
+    fn migrates_first_deployment_curves() {
+        let clock = Clock { slot: 42, ..Clock::default() };
+        let data = legacy_curve_data();
+        assert!(BondingCurveState::LEGACY_LENS.contains(&(8 + data.len())));
+        assert_migrated(&BondingCurveState::from_legacy(&data, &clock).unwrap());
+    }
+
+    #[test]
+    fn migrates_first_deployment_curves_grown_by_extend() {
+        let clock = Clock { slot: 42, ..Clock::default() };
+        let mut data = legacy_curve_data();
+        data.resize(256 - 8, 0);
+        assert!(BondingCurveState::LEGACY_LENS.contains(&(8 + data.len())));
+        assert!(!BondingCurveState::LEGACY_LENS.contains(&BondingCurveState::LEN));
+        assert_migrated(&BondingCurveState::from_legacy(&data, &clock).unwrap());
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Per-wallet buys of one coin during its protected launch window
+#[account]
+#[derive(Default)]
+pub struct BuyerRecord {
+    pub buyer: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub tokens_bought: u64,  // Bought inside the window, capped by max_tokens_per_wallet
+    pub last_buy_slot: u64,  // Used for the cooldown, 0 until the first buy
+    pub bump: u8,
+}
+
+impl BuyerRecord {
+    // 8 (discriminator) + 32 * 2 + 8 * 2 + 1 (bump)
+    pub const LEN: usize = 8 + (32 * 2) + (8 * 2) + 1;
+}
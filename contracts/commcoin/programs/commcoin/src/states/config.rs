@@ -1,26 +1,294 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    errors::CommcoinError,
+    math,
+    states::{CurveParams, CurveType, Versioned, RESERVED_LEN, RESERVED_WORDS},
+};
+
 #[account]
 pub struct Config {
-    // pub authority: Pubkey,     //  authority of the program
-    // pub fee_recipient: Pubkey, //  team wallet address to receive the fee
+    pub version: u8,               //  layout version, see states::version, set by configure
+    pub authority: Pubkey,         //  authority of the program
+    pub pending_authority: Pubkey, //  nominated authority, takes over once it accepts
+    pub fee_recipient: Pubkey,     //  team wallet address to receive the fee
+
+    //  lamports to complete the bonding curve
+    pub curve_limit: u64,
+
+    //  curve token/sol amount config
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub total_token_supply: u64,
+
+    //  platform fees in basis points (100 = 1%)
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    pub migration_fee_bps: u16,
+
+    //  share of each trading fee paid to the coin's vault stakers, in basis points of the fee
+    pub staker_fee_share_bps: u16,
+
+    //  share of the platform fee paid to the referrer of a trade, in basis points of the platform fee
+    pub referral_fee_share_bps: u16,
+
+    //  fee accrued to the coin creator on every trade, in basis points of the trade
+    pub creator_fee_bps: u16,
+
+    //  upper bound for the vesting creator allocation minted at launch, in basis points of the supply
+    pub max_creator_allocation_bps: u16,
+
+    //  curve types creators may pick at launch, one bit per CurveType
+    pub allowed_curve_types: u8,
+    //  parameters copied onto new coins using these curves
+    pub linear_params: CurveParams,
+    pub exponential_params: CurveParams,
+
+    //  limits for creator supplied launch parameters
+    pub launch_bounds: LaunchBounds,
+
+    //  anti-sniping defaults for new coins
+    pub anti_snipe: AntiSnipeConfig,
 
-    // //  lamports to complete the bonding curve
-    // pub curve_limit: u64,
+    //  rules for community vault proposals
+    pub governance: GovernanceConfig,
 
-    // //  curve token/sol amount config
-    // pub initial_virtual_token_reserves: u64,
-    // pub initial_virtual_sol_reserves: u64,
-    // pub initial_real_token_reserves: u64,
-    // pub total_token_supply: u64,
+    //  shortest creator vesting accepted at launch, seconds from start_at to the cliff and to the end
+    pub min_vesting_cliff: i64,
+    pub min_vesting_duration: i64,
 
-    // //  platform fee percentage
-    // pub buy_fee_percent: f64,
-    // pub sell_fee_percent: f64,
-    // pub migration_fee_percent: f64,
+    pub _reserved: [u64; RESERVED_WORDS - 2], //  zeroed, taken by future fields
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct GovernanceConfig {
+    pub min_voting_period: i64,      // seconds
+    pub max_voting_period: i64,      // seconds
+    pub quorum_bps: u16,             // votes needed, in basis points of the vault weight at the snapshot
+    pub min_proposer_weight_bps: u16, // stake weight needed to propose, in basis points of the vault weight
+}
+
+impl GovernanceConfig {
+    pub const LEN: usize = 8 * 2 + 2 * 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct AntiSnipeConfig {
+    pub protected_slots: u64,     // default window length after launch, 0 disables it
+    pub max_wallet_bps: u16,      // default per-wallet cap in basis points of the supply
+    pub cooldown_slots: u64,      // default minimum slots between buys of one wallet
+    pub max_protected_slots: u64, // upper bound for per-coin overrides
+}
+
+impl AntiSnipeConfig {
+    pub const LEN: usize = 8 + 2 + 8 + 8;
+}
+
+//  per-coin anti-sniping window, overrides the config defaults in create_coin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct AntiSnipeParams {
+    pub protected_slots: u64,       // 0 disables the window
+    pub max_tokens_per_wallet: u64, // base units bought per wallet inside the window
+    pub cooldown_slots: u64,        // 0 disables the cooldown
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct LaunchBounds {
+    pub min_token_supply: u64,         // whole tokens, before decimals
+    pub max_token_supply: u64,         // whole tokens, before decimals
+    pub max_decimals: u8,
+    pub min_virtual_sol_reserves: u64, // lamports
+    pub max_virtual_sol_reserves: u64, // lamports
+    pub min_virtual_token_bps: u16,    // virtual token reserves in basis points of the supply
+    pub max_virtual_token_bps: u16,
+}
+
+impl LaunchBounds {
+    pub const LEN: usize = 8 * 2 + 1 + 8 * 2 + 2 * 2;
+}
+
+//  launch parameters a creator can pick in create_coin, base units unless noted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LaunchParams {
+    pub token_total_supply: u64,
+    pub decimals: u8,
+    pub virtual_sol_reserves: u64, // lamports
+    pub virtual_token_reserves: u64,
 }
 
 impl Config {
-    pub const SEED_PREFIX: &'static str = "global-config";
-    pub const LEN: usize = 0;//32 + 32 + 8 + 8 * 4 + 8 * 3;
-}
\ No newline at end of file
+    //  without the discriminator: 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 1 + 32 * 3 + 8 + 8 * 4 + 2 * 7 + 1 + CurveParams::LEN * 2 + LaunchBounds::LEN + AntiSnipeConfig::LEN + GovernanceConfig::LEN + 8 * 2 + (RESERVED_LEN - 16);
+
+    pub const MAX_FEE_BPS: u16 = 1_000; // 10%, upper bound for any single fee
+    pub const MAX_DECIMALS: u8 = 9;
+    pub const DEFAULT_DECIMALS: u8 = 6;
+    pub const BPS_DENOMINATOR: u16 = 10_000;
+
+    //  fee in lamports for `amount`, rounded down
+    pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+        math::bps(amount, fee_bps)
+    }
+
+    //  platform defaults, used when the creator doesn't pass launch parameters
+    pub fn default_launch_params(&self) -> LaunchParams {
+        LaunchParams {
+            token_total_supply: self.total_token_supply,
+            decimals: Self::DEFAULT_DECIMALS,
+            virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            virtual_token_reserves: self.initial_virtual_token_reserves,
+        }
+    }
+
+    //  checks creator supplied parameters against the launch bounds
+    pub fn validate_launch_params(&self, params: &LaunchParams) -> Result<()> {
+        let bounds = &self.launch_bounds;
+        require!(params.decimals <= bounds.max_decimals, CommcoinError::InvalidLaunchParams);
+
+        let whole_tokens = params.token_total_supply / 10u64.pow(params.decimals as u32);
+        require!(
+            whole_tokens >= bounds.min_token_supply && whole_tokens <= bounds.max_token_supply,
+            CommcoinError::InvalidLaunchParams
+        );
+        require!(
+            params.virtual_sol_reserves >= bounds.min_virtual_sol_reserves
+                && params.virtual_sol_reserves <= bounds.max_virtual_sol_reserves,
+            CommcoinError::InvalidLaunchParams
+        );
+
+        let min_virtual_tokens = Self::calculate_fee(params.token_total_supply, bounds.min_virtual_token_bps)?;
+        let max_virtual_tokens = Self::calculate_fee(params.token_total_supply, bounds.max_virtual_token_bps)?;
+        require!(
+            params.virtual_token_reserves >= min_virtual_tokens
+                && params.virtual_token_reserves <= max_virtual_tokens,
+            CommcoinError::InvalidLaunchParams
+        );
+        Ok(())
+    }
+
+    //  tokens sold through the curve for a given supply, same share of supply as the defaults
+    pub fn real_token_reserves_for(&self, token_total_supply: u64) -> Result<u64> {
+        math::to_u64(math::mul_div(
+            token_total_supply as u128,
+            self.initial_real_token_reserves as u128,
+            self.total_token_supply as u128,
+        )?)
+    }
+
+    //  anti-sniping settings for a new coin, creator overrides are bounded by the config
+    pub fn anti_snipe_params(
+        &self,
+        overrides: Option<AntiSnipeParams>,
+        token_total_supply: u64,
+    ) -> Result<AntiSnipeParams> {
+        let params = match overrides {
+            Some(params) => params,
+            None => AntiSnipeParams {
+                protected_slots: self.anti_snipe.protected_slots,
+                max_tokens_per_wallet: Self::calculate_fee(token_total_supply, self.anti_snipe.max_wallet_bps)?,
+                cooldown_slots: self.anti_snipe.cooldown_slots,
+            },
+        };
+        require!(
+            params.protected_slots <= self.anti_snipe.max_protected_slots
+                && (params.protected_slots == 0 || params.max_tokens_per_wallet > 0),
+            CommcoinError::InvalidLaunchWindow
+        );
+        Ok(params)
+    }
+
+    pub fn is_curve_allowed(&self, curve_type: CurveType) -> bool {
+        self.allowed_curve_types & curve_type.mask() != 0
+    }
+
+    pub fn curve_params(&self, curve_type: CurveType) -> CurveParams {
+        match curve_type {
+            CurveType::ConstantProduct => CurveParams::default(),
+            CurveType::Linear => self.linear_params,
+            CurveType::Exponential => self.exponential_params,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.buy_fee_bps <= Self::MAX_FEE_BPS
+                && self.sell_fee_bps <= Self::MAX_FEE_BPS
+                && self.migration_fee_bps <= Self::MAX_FEE_BPS
+                && self.creator_fee_bps <= Self::MAX_FEE_BPS,
+            CommcoinError::InvalidConfig
+        );
+        require!(
+            self.staker_fee_share_bps <= Self::BPS_DENOMINATOR
+                && self.referral_fee_share_bps <= Self::BPS_DENOMINATOR
+                && self.max_creator_allocation_bps < Self::BPS_DENOMINATOR,
+            CommcoinError::InvalidConfig
+        );
+        require!(
+            self.total_token_supply > 0
+                && self.initial_real_token_reserves > 0
+                && self.initial_real_token_reserves <= self.total_token_supply,
+            CommcoinError::InvalidConfig
+        );
+        require!(
+            self.initial_virtual_token_reserves >= self.initial_real_token_reserves
+                && self.initial_virtual_sol_reserves > 0,
+            CommcoinError::InvalidConfig
+        );
+        require!(self.curve_limit > 0, CommcoinError::InvalidConfig);
+        let bounds = &self.launch_bounds;
+        require!(
+            bounds.max_decimals <= Self::MAX_DECIMALS
+                && bounds.min_token_supply > 0
+                && bounds.min_token_supply <= bounds.max_token_supply
+                && bounds.min_virtual_sol_reserves > 0
+                && bounds.min_virtual_sol_reserves <= bounds.max_virtual_sol_reserves
+                && bounds.min_virtual_token_bps <= bounds.max_virtual_token_bps,
+            CommcoinError::InvalidConfig
+        );
+        let anti_snipe = &self.anti_snipe;
+        require!(
+            anti_snipe.protected_slots <= anti_snipe.max_protected_slots
+                && anti_snipe.max_wallet_bps <= Self::BPS_DENOMINATOR
+                && (anti_snipe.protected_slots == 0 || anti_snipe.max_wallet_bps > 0),
+            CommcoinError::InvalidConfig
+        );
+        require!(
+            self.min_vesting_cliff >= 0 && self.min_vesting_duration >= self.min_vesting_cliff,
+            CommcoinError::InvalidConfig
+        );
+        let governance = &self.governance;
+        require!(
+            governance.min_voting_period > 0
+                && governance.min_voting_period <= governance.max_voting_period
+                && governance.quorum_bps <= Self::BPS_DENOMINATOR
+                && governance.min_proposer_weight_bps <= Self::BPS_DENOMINATOR,
+            CommcoinError::InvalidConfig
+        );
+        require!(
+            self.allowed_curve_types != 0 && self.allowed_curve_types & !CurveType::ALL_MASK == 0,
+            CommcoinError::InvalidConfig
+        );
+        for curve_type in [CurveType::Linear, CurveType::Exponential] {
+            if self.is_curve_allowed(curve_type) {
+                self.curve_params(curve_type).validate(curve_type)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//  the first deployment's config was empty and lived under other seeds, configure
+//  creates this one at version 1
+impl Versioned for Config {
+    const CURRENT_VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        //  version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
@@ -0,0 +1,469 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CommcoinError,
+    math::{self, div_ceil, mul, mul_div, to_u64},
+    states::BondingCurveState,
+};
+
+// Prices are quoted in lamports per PRICE_SCALE base units, so sub-lamport prices stay exact
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+
+// Fixed point scale used by the exponential curve
+const EXP_SCALE: u128 = 1_000_000_000_000;
+const E_SCALED: u128 = 2_718_281_828_459; // e * EXP_SCALE
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CurveType {
+    #[default]
+    ConstantProduct, // x * y = k on the virtual reserves
+    Linear,          // price moves linearly from start_price to end_price
+    Exponential,     // price grows by e^(growth_bps / 10_000) across the curve
+}
+
+impl CurveType {
+    // Bit of this curve type in Config::allowed_curve_types
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    pub const ALL_MASK: u8 = 0b111;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct CurveParams {
+    pub start_price: u64, // price of the first token sold, lamports per PRICE_SCALE base units
+    pub end_price: u64,   // linear only, price once the real token reserves are sold out
+    pub growth_bps: u32,  // exponential only, 10_000 = price grows e times across the curve
+}
+
+impl CurveParams {
+    pub const LEN: usize = 8 + 8 + 4;
+
+    pub const MAX_GROWTH_BPS: u32 = 100_000; // e^10, keeps the fixed point math in range
+
+    pub fn validate(&self, curve_type: CurveType) -> Result<()> {
+        match curve_type {
+            CurveType::ConstantProduct => {}
+            CurveType::Linear => require!(
+                self.start_price > 0 && self.end_price >= self.start_price,
+                CommcoinError::InvalidConfig
+            ),
+            CurveType::Exponential => require!(
+                self.start_price > 0
+                    && self.growth_bps > 0
+                    && self.growth_bps <= Self::MAX_GROWTH_BPS,
+                CommcoinError::InvalidConfig
+            ),
+        }
+        Ok(())
+    }
+}
+
+// Pricing shared by buy and sell. Implementations only do the math,
+// amount and reserve checks common to every curve live on BondingCurveState.
+pub trait CurvePricing {
+    // Lamports to take `token_amount` out of the curve, rounded up
+    fn buy_cost(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64>;
+    // Lamports for returning `token_amount` to the curve, rounded down
+    fn sell_output(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64>;
+    // Marginal price at the current reserves, lamports per PRICE_SCALE base units
+    fn spot_price(&self, curve: &BondingCurveState) -> Result<u64>;
+    // Tokens `sol` lamports buy at the current reserves, from the inverse of buy_cost.
+    // Exact for the constant product curve, off by the fixed point rounding for the others,
+    // so callers check the result against buy_cost. Not capped by the real token reserves.
+    fn tokens_for(&self, curve: &BondingCurveState, sol: u64) -> Result<u64>;
+}
+
+pub struct ConstantProduct;
+pub struct Linear;
+pub struct Exponential;
+
+impl CurveType {
+    pub fn pricing(self) -> &'static dyn CurvePricing {
+        match self {
+            CurveType::ConstantProduct => &ConstantProduct,
+            CurveType::Linear => &Linear,
+            CurveType::Exponential => &Exponential,
+        }
+    }
+}
+
+impl CurvePricing for ConstantProduct {
+    fn buy_cost(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        require!(
+            token_amount < curve.virtual_token_reserves,
+            CommcoinError::InsufficientReserves
+        );
+        let sol_reserves = curve.virtual_sol_reserves as u128;
+        let token_reserves = curve.virtual_token_reserves as u128;
+        let amount = token_amount as u128;
+
+        to_u64(math::mul_div_ceil(sol_reserves, amount, token_reserves - amount)?)
+    }
+
+    fn sell_output(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        let sol_reserves = curve.virtual_sol_reserves as u128;
+        let token_reserves = curve.virtual_token_reserves as u128;
+        let amount = token_amount as u128;
+
+        to_u64(mul_div(sol_reserves, amount, math::add_u128(token_reserves, amount)?)?)
+    }
+
+    fn spot_price(&self, curve: &BondingCurveState) -> Result<u64> {
+        to_u64(mul_div(
+            curve.virtual_sol_reserves as u128,
+            PRICE_SCALE,
+            curve.virtual_token_reserves as u128,
+        )?)
+    }
+
+    // Largest amount with sol_reserves * amount <= sol * (token_reserves - amount)
+    fn tokens_for(&self, curve: &BondingCurveState, sol: u64) -> Result<u64> {
+        to_u64(mul_div(
+            sol as u128,
+            curve.virtual_token_reserves as u128,
+            math::add_u128(curve.virtual_sol_reserves as u128, sol as u128)?,
+        )?)
+    }
+}
+
+impl Linear {
+    // Area under the price line between `from` and `to` tokens sold, scaled by PRICE_SCALE.
+    // price(s) = start + (end - start) * s / range
+    fn area(curve: &BondingCurveState, from: u64, to: u64, round_up: bool) -> Result<u128> {
+        let params = &curve.curve_params;
+        let range = curve.initial_real_token_reserves as u128;
+        let (from, to) = (from as u128, to as u128);
+        let amount = to - from;
+
+        let flat = mul(params.start_price as u128, amount)?;
+        // to^2 - from^2 = amount * (to + from), halved by the integral
+        let squares = mul(amount, to + from)?;
+        let slope_area = if round_up {
+            div_ceil(squares, 2 * range)?
+        } else {
+            math::div(squares, 2 * range)?
+        };
+        let slope_area = mul(slope_area, (params.end_price - params.start_price) as u128)?;
+        math::add_u128(flat, slope_area)
+    }
+}
+
+impl CurvePricing for Linear {
+    fn buy_cost(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        let sold = curve.tokens_sold()?;
+        let area = Self::area(curve, sold, math::add(sold, token_amount)?, true)?;
+        to_u64(div_ceil(area, PRICE_SCALE)?)
+    }
+
+    fn sell_output(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        let sold = curve.tokens_sold()?;
+        require!(token_amount <= sold, CommcoinError::InsufficientReserves);
+        let area = Self::area(curve, sold - token_amount, sold, false)?;
+        to_u64(math::div(area, PRICE_SCALE)?)
+    }
+
+    fn spot_price(&self, curve: &BondingCurveState) -> Result<u64> {
+        let params = &curve.curve_params;
+        let slope = mul_div(
+            (params.end_price - params.start_price) as u128,
+            curve.tokens_sold()? as u128,
+            curve.initial_real_token_reserves as u128,
+        )?;
+        to_u64(math::add_u128(params.start_price as u128, slope)?)
+    }
+
+    // Root of price * t + slope * t^2 / (2 * range) = sol * PRICE_SCALE, written as
+    // t = 2 * budget / (price + sqrt(price^2 + 2 * slope * budget / range)) to stay in range.
+    // The price carries PRICE_SCALE more digits, at the low prices curves start from
+    // the square root would otherwise round the slope term away.
+    fn tokens_for(&self, curve: &BondingCurveState, sol: u64) -> Result<u64> {
+        let params = &curve.curve_params;
+        let range = curve.initial_real_token_reserves as u128;
+        let slope = (params.end_price - params.start_price) as u128;
+        let budget = mul(sol as u128, PRICE_SCALE)?;
+        let price = math::add_u128(
+            mul(params.start_price as u128, PRICE_SCALE)?,
+            mul_div(mul(slope, curve.tokens_sold()? as u128)?, PRICE_SCALE, range)?,
+        )?;
+        let growth = mul(mul_div(mul(mul(2, slope)?, budget)?, PRICE_SCALE, range)?, PRICE_SCALE)?;
+        let root = math::sqrt(math::add_u128(mul(price, price)?, growth)?);
+        to_u64(mul_div(mul(2, budget)?, PRICE_SCALE, math::add_u128(price, root)?)?)
+    }
+}
+
+impl Exponential {
+    // e^x for x scaled by EXP_SCALE, rounded down
+    fn exp(x: u128) -> Result<u128> {
+        let whole = x / EXP_SCALE;
+        let frac = x % EXP_SCALE;
+
+        // Taylor series for the fractional part, converges fast since frac < 1
+        let mut term = EXP_SCALE;
+        let mut sum = EXP_SCALE;
+        for i in 1..=20u128 {
+            term = mul_div(term, frac, EXP_SCALE)? / i;
+            if term == 0 {
+                break;
+            }
+            sum = math::add_u128(sum, term)?;
+        }
+
+        for _ in 0..whole {
+            sum = mul_div(sum, E_SCALED, EXP_SCALE)?;
+        }
+        Ok(sum)
+    }
+
+    // ln(y) for y >= 1, both scaled by EXP_SCALE, rounded down
+    fn ln(y: u128) -> Result<u128> {
+        require!(y >= EXP_SCALE, CommcoinError::MathOverflow);
+        let mut whole = 0u128;
+        let mut m = y;
+        while m >= E_SCALED {
+            m = mul_div(m, EXP_SCALE, E_SCALED)?;
+            whole += 1;
+        }
+
+        // ln(m) = 2 * atanh(z) with z = (m - 1) / (m + 1) < 0.47 for m < e
+        let z = mul_div(m - EXP_SCALE, EXP_SCALE, m + EXP_SCALE)?;
+        let z_squared = mul_div(z, z, EXP_SCALE)?;
+        let mut term = z;
+        let mut sum = z;
+        for i in 1..=40u128 {
+            term = mul_div(term, z_squared, EXP_SCALE)?;
+            if term == 0 {
+                break;
+            }
+            sum = math::add_u128(sum, term / (2 * i + 1))?;
+        }
+        math::add_u128(mul(whole, EXP_SCALE)?, mul(sum, 2)?)
+    }
+
+    // Exponent at `sold` tokens, scaled by EXP_SCALE
+    fn exponent(curve: &BondingCurveState, sold: u64) -> Result<u128> {
+        let progress = mul_div(sold as u128, EXP_SCALE, curve.initial_real_token_reserves as u128)?;
+        mul_div(progress, curve.curve_params.growth_bps as u128, 10_000)
+    }
+
+    // Lamports between `from` and `to` tokens sold, before the final division.
+    // price(s) = start * e^(g * s / range), integral = start * range / g * (e^(g*to/range) - e^(g*from/range))
+    fn area(curve: &BondingCurveState, from: u64, to: u64) -> Result<(u128, u128)> {
+        let params = &curve.curve_params;
+        let growth = math::sub_u128(
+            Self::exp(Self::exponent(curve, to)?)?,
+            Self::exp(Self::exponent(curve, from)?)?,
+        )?;
+        let numerator = mul_div(growth, curve.initial_real_token_reserves as u128, EXP_SCALE)?;
+        let numerator = mul(mul(numerator, params.start_price as u128)?, 10_000)?;
+        let denominator = mul(params.growth_bps as u128, PRICE_SCALE)?;
+        Ok((numerator, denominator))
+    }
+}
+
+impl CurvePricing for Exponential {
+    fn buy_cost(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        let sold = curve.tokens_sold()?;
+        let (numerator, denominator) = Self::area(curve, sold, math::add(sold, token_amount)?)?;
+        to_u64(div_ceil(numerator, denominator)?)
+    }
+
+    fn sell_output(&self, curve: &BondingCurveState, token_amount: u64) -> Result<u64> {
+        let sold = curve.tokens_sold()?;
+        require!(token_amount <= sold, CommcoinError::InsufficientReserves);
+        let (numerator, denominator) = Self::area(curve, sold - token_amount, sold)?;
+        to_u64(math::div(numerator, denominator)?)
+    }
+
+    fn spot_price(&self, curve: &BondingCurveState) -> Result<u64> {
+        let growth = Self::exp(Self::exponent(curve, curve.tokens_sold()?)?)?;
+        to_u64(mul_div(curve.curve_params.start_price as u128, growth, EXP_SCALE)?)
+    }
+
+    // Inverse of `area`: e^(g * to / range) = e^(g * sold / range) + sol * g * PRICE_SCALE / (start * range * 10_000)
+    fn tokens_for(&self, curve: &BondingCurveState, sol: u64) -> Result<u64> {
+        let params = &curve.curve_params;
+        let range = curve.initial_real_token_reserves as u128;
+        let sold = curve.tokens_sold()?;
+        let growth = mul_div(
+            mul(sol as u128, PRICE_SCALE)?,
+            EXP_SCALE,
+            mul(range, params.start_price as u128)?,
+        )?;
+        let growth = mul_div(growth, params.growth_bps as u128, 10_000)?;
+        let target = math::add_u128(Self::exp(Self::exponent(curve, sold)?)?, growth)?;
+        let to = mul_div(
+            Self::ln(target)?,
+            mul(range, 10_000)?,
+            mul(EXP_SCALE, params.growth_bps as u128)?,
+        )?;
+        to_u64(to.saturating_sub(sold as u128))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens, 6 decimals
+    const REAL_TOKENS: u64 = 793_100_000_000_000;
+
+    fn curve(curve_type: CurveType, curve_params: CurveParams) -> BondingCurveState {
+        BondingCurveState {
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_token_reserves: REAL_TOKENS,
+            initial_real_token_reserves: REAL_TOKENS,
+            token_total_supply: SUPPLY,
+            curve_type,
+            curve_params,
+            ..Default::default()
+        }
+    }
+
+    fn linear() -> BondingCurveState {
+        curve(CurveType::Linear, CurveParams { start_price: 28, end_price: 400, growth_bps: 0 })
+    }
+
+    fn exponential() -> BondingCurveState {
+        curve(
+            CurveType::Exponential,
+            CurveParams { start_price: 28, end_price: 0, growth_bps: CurveParams::MAX_GROWTH_BPS },
+        )
+    }
+
+    // Reserve moves of apply_buy/apply_sell, without the price accumulator that needs the clock
+    fn buy(curve: &mut BondingCurveState, tokens: u64) -> u64 {
+        let cost = curve.get_buy_price(tokens).unwrap();
+        curve.virtual_token_reserves -= tokens;
+        curve.real_token_reserves -= tokens;
+        curve.virtual_sol_reserves += cost;
+        curve.real_sol_reserves += cost;
+        cost
+    }
+
+    fn sell(curve: &mut BondingCurveState, tokens: u64) -> u64 {
+        let output = curve.get_sell_price(tokens).unwrap();
+        assert!(output <= curve.real_sol_reserves);
+        curve.virtual_token_reserves += tokens;
+        curve.real_token_reserves += tokens;
+        curve.virtual_sol_reserves -= output;
+        curve.real_sol_reserves -= output;
+        output
+    }
+
+    fn assert_round_trips_without_profit(mut curve: BondingCurveState) {
+        // Single buy sold straight back
+        for tokens in [1, 999, 1_000_000, 50_000_000_000_000, REAL_TOKENS] {
+            let mut fresh = curve.clone();
+            let cost = buy(&mut fresh, tokens);
+            assert!(sell(&mut fresh, tokens) <= cost, "{:?} profit on {} tokens", curve.curve_type, tokens);
+        }
+
+        // Several buys sold back in different chunks, the curve never pays out more than it took
+        let bought = [7, 123_456_789, 10_000_000_000_000, 300_000_000_000_001];
+        let paid: u64 = bought.iter().map(|&tokens| buy(&mut curve, tokens)).sum();
+        let total: u64 = bought.iter().sum();
+        let mut received = 0;
+        let mut left = total;
+        for chunk in [total / 3, 1, total / 5] {
+            received += sell(&mut curve, chunk);
+            left -= chunk;
+        }
+        received += sell(&mut curve, left);
+        assert!(received <= paid);
+        assert_eq!(curve.real_token_reserves, REAL_TOKENS);
+    }
+
+    #[test]
+    fn linear_round_trip_is_not_profitable() {
+        assert_round_trips_without_profit(linear());
+    }
+
+    #[test]
+    fn exponential_round_trip_is_not_profitable() {
+        assert_round_trips_without_profit(exponential());
+    }
+
+    #[test]
+    fn constant_product_round_trip_is_not_profitable() {
+        assert_round_trips_without_profit(curve(CurveType::ConstantProduct, CurveParams::default()));
+    }
+
+    #[test]
+    fn exp_is_accurate_up_to_max_growth() {
+        let max_exponent = CurveParams::MAX_GROWTH_BPS as u128 * EXP_SCALE / 10_000;
+        for (x, expected) in [
+            (0, 1.0_f64),
+            (EXP_SCALE / 2, 1.648_721_270_700_128),
+            (EXP_SCALE, std::f64::consts::E),
+            (max_exponent - EXP_SCALE / 2, 13_359.726_829_661_873),
+            (max_exponent, 22_026.465_794_806_718),
+        ] {
+            let actual = Exponential::exp(x).unwrap() as f64 / EXP_SCALE as f64;
+            let error = (actual - expected).abs() / expected;
+            assert!(error < 1e-9, "e^{} = {}, expected {}", x, actual, expected);
+            // Rounded down, never above the true value beyond float noise
+            assert!(actual <= expected * (1.0 + 1e-12));
+        }
+    }
+
+    #[test]
+    fn exponential_prices_span_the_configured_growth() {
+        let mut curve = exponential();
+        let start = curve.spot_price().unwrap();
+        buy(&mut curve, REAL_TOKENS);
+        let end = curve.spot_price().unwrap();
+        assert_eq!(start, 28);
+        // 28 * e^10, less the rounding of the fixed point math
+        assert!((616_700..=616_741).contains(&end), "end price {}", end);
+    }
+
+    #[test]
+    fn ln_inverts_exp() {
+        let max_exponent = CurveParams::MAX_GROWTH_BPS as u128 * EXP_SCALE / 10_000;
+        for x in [0, 1, EXP_SCALE / 3, EXP_SCALE, 7 * EXP_SCALE / 2, max_exponent] {
+            let actual = Exponential::ln(Exponential::exp(x).unwrap()).unwrap();
+            assert!(actual.abs_diff(x) <= 100, "ln(e^{}) = {}", x, actual);
+        }
+    }
+
+    // Largest amount whose buy cost fits in `sol`, by bisection over buy_cost
+    fn tokens_for_by_search(curve: &BondingCurveState, sol: u64) -> u64 {
+        let (mut low, mut high) = (0, curve.real_token_reserves);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if curve.get_buy_price(mid).unwrap() <= sol {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    #[test]
+    fn tokens_for_inverts_buy_cost() {
+        let constant_product = curve(CurveType::ConstantProduct, CurveParams::default());
+        // The exponential price moves in steps of range / EXP_SCALE tokens, the estimate can miss by a step or two
+        for (fresh, tolerance) in [(constant_product, 0), (linear(), 10), (exponential(), 2_000)] {
+            for sold in [0, 1_000_000, REAL_TOKENS / 2] {
+                let mut curve = fresh.clone();
+                if sold > 0 {
+                    buy(&mut curve, sold);
+                }
+                for sol in [1, 1_000, 1_000_000_000, 5_000_000_000] {
+                    let estimate = curve.curve_type.pricing().tokens_for(&curve, sol).unwrap();
+                    let estimate = estimate.min(curve.real_token_reserves);
+                    let exact = tokens_for_by_search(&curve, sol);
+                    assert!(
+                        estimate.abs_diff(exact) <= tolerance,
+                        "{:?} after {} sold, {} lamports: {} tokens, expected {}",
+                        curve.curve_type, sold, sol, estimate, exact
+                    );
+                }
+            }
+        }
+    }
+}
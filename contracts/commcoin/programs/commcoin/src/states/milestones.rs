@@ -0,0 +1,287 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CommcoinError,
+    events::MilestoneReached,
+    math,
+    states::{VaultConfig, VaultError, Versioned, RESERVED_LEN, RESERVED_WORDS},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Milestone {
+    pub threshold: u64,      // vault locked_tokens that reaches it
+    pub release_amount: u64, // creator-locked tokens unlocked for the creator, 0 for none
+    pub perk: u8,            // bit set in VaultMilestones::perks once reached, NO_PERK for none
+    pub reached: bool,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 8 + 1 + 1;
+}
+
+// Milestone as passed by the creator, the threshold is relative to the token supply
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct MilestoneArgs {
+    pub threshold_bps: u16, // locked tokens in basis points of the supply, e.g. 1_000 = 10%
+    pub release_amount: u64,
+    pub perk: u8,
+}
+
+// Staking milestones of one vault, next to VaultConfig's single goal.
+// Milestones are reached in order on the vault's locked tokens and stay reached when stake leaves again.
+// Flexible stake doesn't count, it could pass a milestone and leave within the same transaction.
+// Milestones still open at the deadline can't be reached anymore, their release goes back to the creator.
+#[account]
+#[derive(Default)]
+pub struct VaultMilestones {
+    pub version: u8,           // Layout version, see states::version
+    pub vault: Pubkey,
+    pub creator: Pubkey,       // Deposited the locked allocation, receives it as milestones are reached
+    pub count: u8,             // Milestones in use, from the start of `milestones`
+    pub milestones: [Milestone; VaultMilestones::MAX_MILESTONES],
+    pub perks: u32,            // One bit per unlocked perk, meaning is up to the community
+    pub locked_tokens: u64,    // Allocation that arrived in the milestone token account
+    pub unlocked_tokens: u64,  // Released by reached milestones, capped by locked_tokens
+    pub claimed_tokens: u64,   // Paid out to the creator so far
+    pub bump: u8,
+    pub deadline: i64,         // Unix timestamp, milestones are reached before it
+    pub _reserved: [u64; RESERVED_WORDS - 1], // Zeroed, taken by future fields
+}
+
+impl VaultMilestones {
+    pub const MAX_MILESTONES: usize = 5;
+    pub const NO_PERK: u8 = u8::MAX;
+
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + (32 * 2) + 1 + Milestone::LEN * Self::MAX_MILESTONES + 4 + (8 * 3) + 1 + 8 + (RESERVED_LEN - 8);
+
+    // Thresholds must be strictly increasing and still ahead of the vault, the deadline ahead of `now`
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        vault: &VaultConfig,
+        vault_key: Pubkey,
+        args: &[MilestoneArgs],
+        token_supply: u64,
+        deadline: i64,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            !args.is_empty() && args.len() <= Self::MAX_MILESTONES,
+            VaultError::InvalidMilestones
+        );
+        require!(deadline > now, VaultError::InvalidMilestones);
+
+        let mut previous = vault.locked_tokens;
+        for (milestone, arg) in self.milestones.iter_mut().zip(args) {
+            let threshold = math::bps(token_supply, arg.threshold_bps)?;
+            require!(threshold > previous, VaultError::InvalidMilestones);
+            require!(
+                arg.perk < u32::BITS as u8 || arg.perk == Self::NO_PERK,
+                VaultError::InvalidMilestones
+            );
+            *milestone = Milestone {
+                threshold,
+                release_amount: arg.release_amount,
+                perk: arg.perk,
+                reached: false,
+            };
+            previous = threshold;
+        }
+
+        self.version = Self::CURRENT_VERSION;
+        self.vault = vault_key;
+        self.creator = vault.authority;
+        self.count = args.len() as u8;
+        self.deadline = deadline;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn total_release(&self) -> Result<u64> {
+        self.milestones[..self.count as usize]
+            .iter()
+            .try_fold(0, |total, milestone| math::add(total, milestone.release_amount))
+    }
+
+    // Marks every milestone the vault's locked tokens have passed, unlocking its allocation and perk
+    pub fn reach(&mut self, vault: &VaultConfig, timestamp: i64) -> Result<()> {
+        if timestamp >= self.deadline {
+            return Ok(());
+        }
+        for index in 0..self.count as usize {
+            let milestone = self.milestones[index];
+            if milestone.reached {
+                continue;
+            }
+            if vault.locked_tokens < milestone.threshold {
+                break;
+            }
+
+            self.milestones[index].reached = true;
+            if milestone.perk != Self::NO_PERK {
+                self.perks |= 1 << milestone.perk;
+            }
+            self.unlocked_tokens = math::add(self.unlocked_tokens, milestone.release_amount)?.min(self.locked_tokens);
+
+            emit!(MilestoneReached {
+                vault: self.vault,
+                mint: vault.token_mint,
+                index: index as u8,
+                threshold: milestone.threshold,
+                locked_tokens: vault.locked_tokens,
+                release_amount: milestone.release_amount,
+                perk: milestone.perk,
+                perks: self.perks,
+                timestamp,
+            });
+            msg!("Vault milestone {} reached: locked tokens {}, threshold {}", index, vault.locked_tokens, milestone.threshold);
+        }
+        Ok(())
+    }
+
+    // Unlocked tokens not claimed yet. From the deadline on that's the whole locked
+    // allocation, the release of the milestones that weren't reached is reclaimed.
+    pub fn claimable(&self, now: i64) -> Result<u64> {
+        let unlocked = if now >= self.deadline { self.locked_tokens } else { self.unlocked_tokens };
+        math::sub(unlocked, self.claimed_tokens)
+    }
+}
+
+impl Versioned for VaultMilestones {
+    const CURRENT_VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPLY: u64 = 1_000_000_000;
+    const DEADLINE: i64 = 1_000;
+
+    fn args(threshold_bps: u16, release_amount: u64, perk: u8) -> MilestoneArgs {
+        MilestoneArgs { threshold_bps, release_amount, perk }
+    }
+
+    fn milestones(vault: &VaultConfig) -> VaultMilestones {
+        let mut milestones = VaultMilestones::default();
+        milestones
+            .initialize(
+                vault,
+                Pubkey::new_unique(),
+                &[args(1_000, 100, 0), args(2_500, 200, VaultMilestones::NO_PERK), args(5_000, 300, 7)],
+                SUPPLY,
+                DEADLINE,
+                0,
+                255,
+            )
+            .unwrap();
+        milestones.locked_tokens = 500;
+        milestones
+    }
+
+    fn reached(milestones: &VaultMilestones) -> Vec<bool> {
+        milestones.milestones[..milestones.count as usize].iter().map(|milestone| milestone.reached).collect()
+    }
+
+    #[test]
+    fn thresholds_must_increase_past_the_vault() {
+        let mut vault = VaultConfig::default();
+        for invalid in [
+            vec![],
+            vec![args(2_500, 0, 0), args(1_000, 0, 1)],
+            vec![args(1_000, 0, 0), args(1_000, 0, 1)],
+            vec![args(1_000, 0, 32)],
+        ] {
+            assert!(VaultMilestones::default()
+                .initialize(&vault, Pubkey::new_unique(), &invalid, SUPPLY, DEADLINE, 0, 255)
+                .is_err());
+        }
+
+        // The deadline must be ahead
+        assert!(VaultMilestones::default()
+            .initialize(&vault, Pubkey::new_unique(), &[args(1_000, 0, 0)], SUPPLY, DEADLINE, DEADLINE, 255)
+            .is_err());
+
+        vault.locked_tokens = SUPPLY / 10;
+        assert!(VaultMilestones::default()
+            .initialize(&vault, Pubkey::new_unique(), &[args(1_000, 0, 0)], SUPPLY, DEADLINE, 0, 255)
+            .is_err());
+    }
+
+    #[test]
+    fn only_locked_tokens_reach_milestones() {
+        let mut vault = VaultConfig { current_tokens_staked: SUPPLY, total_weight: SUPPLY, ..Default::default() };
+        let mut milestones = milestones(&vault);
+
+        // Flexible stake alone, however large, reaches nothing
+        milestones.reach(&vault, 0).unwrap();
+        assert_eq!(reached(&milestones), [false, false, false]);
+
+        vault.locked_tokens = SUPPLY / 4;
+        milestones.reach(&vault, 0).unwrap();
+        assert_eq!(reached(&milestones), [true, true, false]);
+        assert_eq!(milestones.perks, 1);
+        assert_eq!(milestones.claimable(0).unwrap(), 300);
+    }
+
+    #[test]
+    fn lockup_multipliers_dont_count_toward_milestones() {
+        let mut vault = VaultConfig::default();
+        let mut milestones = milestones(&vault);
+
+        // 20% of the supply locked at 2x is 40% worth of weight, still short of the 25% milestone
+        vault.locked_tokens = SUPPLY / 5;
+        vault.total_weight = SUPPLY / 5 * 2;
+        milestones.reach(&vault, 0).unwrap();
+        assert_eq!(reached(&milestones), [true, false, false]);
+    }
+
+    #[test]
+    fn reached_milestones_stay_reached_and_release_up_to_the_locked_tokens() {
+        let mut vault = VaultConfig::default();
+        let mut milestones = milestones(&vault);
+        vault.locked_tokens = SUPPLY;
+        milestones.reach(&vault, 0).unwrap();
+        assert_eq!(reached(&milestones), [true, true, true]);
+        assert_eq!(milestones.perks, 1 | 1 << 7);
+        // 600 released against 500 locked
+        assert_eq!(milestones.unlocked_tokens, 500);
+
+        vault.locked_tokens = 0;
+        milestones.reach(&vault, 0).unwrap();
+        assert_eq!(reached(&milestones), [true, true, true]);
+        assert_eq!(milestones.unlocked_tokens, 500);
+    }
+
+    #[test]
+    fn creator_reclaims_unreached_releases_after_the_deadline() {
+        let mut vault = VaultConfig::default();
+        let mut milestones = milestones(&vault);
+        vault.locked_tokens = SUPPLY / 10;
+        milestones.reach(&vault, DEADLINE - 1).unwrap();
+        assert_eq!(milestones.claimable(DEADLINE - 1).unwrap(), 100);
+        milestones.claimed_tokens = 100;
+
+        // Too late to reach the rest
+        vault.locked_tokens = SUPPLY;
+        milestones.reach(&vault, DEADLINE).unwrap();
+        assert_eq!(reached(&milestones), [true, false, false]);
+        assert_eq!(milestones.perks, 1);
+
+        assert_eq!(milestones.claimable(DEADLINE - 1).unwrap(), 0);
+        assert_eq!(milestones.claimable(DEADLINE).unwrap(), 400);
+        milestones.claimed_tokens = 500;
+        assert_eq!(milestones.claimable(DEADLINE).unwrap(), 0);
+    }
+}
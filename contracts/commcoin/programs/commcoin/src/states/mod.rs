@@ -1,6 +1,18 @@
+pub mod version;
+pub use version::*;
 pub mod config;
 pub use config::*;
+pub mod curve;
+pub use curve::*;
 pub mod bonding_curve;
 pub use bonding_curve::*;
 pub mod vault;
-pub use vault::*;
\ No newline at end of file
+pub use vault::*;
+pub mod buyer_record;
+pub use buyer_record::*;
+pub mod milestones;
+pub use milestones::*;
+pub mod proposal;
+pub use proposal::*;
+pub mod vesting;
+pub use vesting::*;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CommcoinError,
+    math,
+    states::{Config, Versioned, RESERVED_LEN, RESERVED_WORDS},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ProposalStatus {
+    #[default]
+    Active,   // open for votes until voting_ends_at
+    Passed,   // quorum reached and more yes than no
+    Rejected,
+}
+
+// Yes/no proposal of a community vault. The text lives off-chain at `uri`,
+// the outcome is what the community acts on for vault rewards and treasury funds.
+#[account]
+#[derive(Default)]
+pub struct Proposal {
+    pub version: u8,            // Layout version, see states::version
+    pub vault: Pubkey,
+    pub id: u64,                // Index within the vault, part of the PDA seeds
+    pub proposer: Pubkey,
+    pub title: String,          // Max MAX_TITLE_LEN
+    pub uri: String,            // Max MAX_URI_LEN, full description
+    pub snapshot_slot: u64,     // Creation slot, stakers vote with their weight before it
+    pub snapshot_weight: u64,   // Vault total_weight at creation, quorum is taken from it
+    pub voting_ends_at: i64,    // Unix timestamp
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub status: ProposalStatus,
+    pub bump: u8,
+    pub _reserved: [u64; RESERVED_WORDS], // Zeroed, taken by future fields
+}
+
+impl Proposal {
+    pub const MAX_TITLE_LEN: usize = 64;
+    pub const MAX_URI_LEN: usize = 200;
+
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 32 + (4 + Self::MAX_TITLE_LEN) + (4 + Self::MAX_URI_LEN)
+        + (8 * 2) + 8 + (8 * 2) + 1 + 1 + RESERVED_LEN;
+
+    pub fn is_open(&self, now: i64) -> bool {
+        self.status == ProposalStatus::Active && now < self.voting_ends_at
+    }
+
+    pub fn record_vote(&mut self, support: bool, weight: u64) -> Result<()> {
+        if support {
+            self.yes_weight = math::add(self.yes_weight, weight)?;
+        } else {
+            self.no_weight = math::add(self.no_weight, weight)?;
+        }
+        Ok(())
+    }
+
+    // Outcome once voting has ended, quorum counts both sides
+    pub fn outcome(&self, config: &Config) -> Result<ProposalStatus> {
+        let quorum = math::bps(self.snapshot_weight, config.governance.quorum_bps)?;
+        let turnout = math::add(self.yes_weight, self.no_weight)?;
+        Ok(if turnout >= quorum && self.yes_weight > self.no_weight {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        })
+    }
+}
+
+impl Versioned for Proposal {
+    const CURRENT_VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+// One per voter and proposal, its existence is what stops a second vote
+#[account]
+#[derive(Default)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    // 8 (discriminator) + 32 * 2 + 1 + 8 + 1 (bump)
+    pub const LEN: usize = 8 + (32 * 2) + 1 + 8 + 1;
+}
@@ -1,36 +1,354 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    errors::CommcoinError,
+    math,
+    states::{Versioned, RESERVED_LEN, RESERVED_WORDS},
+};
+
 #[account]
 #[derive(Default)]
 pub struct VaultConfig {
+    pub version: u8,                 // Layout version, see states::version
     pub name: String,                // Max 50 chars, e.g. "My Community Vault"
     pub authority: Pubkey,           // Pubkey that can manage the vault (e.g., update goal - optional)
     pub token_mint: Pubkey,          // Mint of the token being staked
     pub vault_token_account: Pubkey, // PDA holding all staked tokens
-    pub goal_tokens_to_reach: u64,   // The target amount of tokens to be staked
+    pub goal_tokens_to_reach: u64,   // The target of locked tokens, see locked_tokens
     pub current_tokens_staked: u64,  // Current total tokens staked in this vault
     pub goal_reached: bool,          // Flag indicating if the goal has been met
     pub bump_config: u8,             // Bump seed for this VaultConfig PDA
     pub bump_token_vault: u8,        // Bump seed for the vault_token_account PDA
+    pub acc_reward_per_share: u128,  // Lamports earned per unit of stake weight, scaled by REWARD_PRECISION
+    pub total_rewards: u64,          // Lamports paid into the vault for stakers since launch
+    pub total_weight: u64,           // Sum of the stakers' weighted stakes, drives rewards and goal progress
+    pub proposal_count: u64,         // Proposals created so far, the next proposal id
+    pub undistributed_rewards: u64,  // Lamports lost to rounding in acc_reward_per_share, carried into the next distribution
+    pub locked_tokens: u64,          // Part of current_tokens_staked under a lockup, the goal and milestones are reached on it
+    pub _reserved: [u64; RESERVED_WORDS - 4], // Zeroed, taken by future fields
 }
 
 impl VaultConfig {
     // Calculate space needed for the account
-    // 8 (discriminator) + 32 (mint) + 32 (creator) + 8*4 (reserves) + 1 (bump) + padding/future fields
-    pub const LEN: usize = 8 + (4 + 50) + (32 * 3) + (8 * 2) + 1 + (1 * 2);
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + (4 + 50) + (32 * 3) + (8 * 2) + 1 + (1 * 2) + 16 + 8 + 8 + 8 + 8 + 8 + (RESERVED_LEN - 32);
+
+    pub const MAX_NAME_LEN: usize = 50;
+
+    pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        name: String,
+        goal_tokens_to_reach: u64,
+        authority: Pubkey,
+        token_mint: Pubkey,
+        vault_token_account: Pubkey,
+        bump_config: u8,
+        bump_token_vault: u8,
+    ) {
+        self.version = Self::CURRENT_VERSION;
+        self.name = name;
+        self.authority = authority;
+        self.token_mint = token_mint;
+        self.vault_token_account = vault_token_account;
+        self.goal_tokens_to_reach = goal_tokens_to_reach;
+        self.current_tokens_staked = 0;
+        self.goal_reached = false;
+        self.bump_config = bump_config;
+        self.bump_token_vault = bump_token_vault;
+        self.acc_reward_per_share = 0;
+        self.total_rewards = 0;
+        self.total_weight = 0;
+        self.proposal_count = 0;
+        self.undistributed_rewards = 0;
+        self.locked_tokens = 0;
+    }
+
+    // Splits `amount` lamports, already sent to this account, across the current stake weight.
+    // What the per-share rounding can't credit is carried into the next distribution.
+    pub fn distribute_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(self.total_weight > 0, VaultError::NoStakers);
+        let distributable = math::add(amount, self.undistributed_rewards)?;
+        let per_share = math::mul_div(
+            distributable as u128,
+            Self::REWARD_PRECISION,
+            self.total_weight as u128,
+        )?;
+        // Rounded up, so the carry never exceeds what is really left over
+        let credited = math::to_u64(math::mul_div_ceil(
+            per_share,
+            self.total_weight as u128,
+            Self::REWARD_PRECISION,
+        )?)?;
+        self.undistributed_rewards = math::sub(distributable, credited)?;
+        self.acc_reward_per_share = math::add_u128(self.acc_reward_per_share, per_share)?;
+        self.total_rewards = math::add(self.total_rewards, amount)?;
+        Ok(())
+    }
+
+    // Run after every stake change, staked tokens are always fully held by the vault
+    pub fn check_invariants(&self, token_vault_balance: u64) -> Result<()> {
+        require!(
+            token_vault_balance >= self.current_tokens_staked,
+            CommcoinError::InvariantViolated
+        );
+        Ok(())
+    }
+
+    // Run after every reward payout, rewards are paid on top of the rent-exempt balance only
+    pub fn check_solvency(&self, vault_info: &AccountInfo) -> Result<()> {
+        require!(
+            vault_info.lamports() >= Rent::get()?.minimum_balance(vault_info.data_len()),
+            CommcoinError::InvariantViolated
+        );
+        Ok(())
+    }
+}
+
+// Layout of the vaults created by the first deployment, read once by migrate_account
+#[derive(AnchorDeserialize)]
+struct LegacyVaultConfig {
+    name: String,
+    authority: Pubkey,
+    token_mint: Pubkey,
+    vault_token_account: Pubkey,
+    goal_tokens_to_reach: u64,
+    current_tokens_staked: u64,
+    goal_reached: bool,
+    bump_config: u8,
+    bump_token_vault: u8,
+}
+
+impl Versioned for VaultConfig {
+    const CURRENT_VERSION: u8 = 1;
+
+    const LEGACY_LENS: &'static [usize] = &[8 + (4 + 50) + (32 * 3) + (8 * 2) + 1 + 2];
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    // Stakes made before lockups are Flexible and count at 1x, their UserStakeInfo
+    // migrates to weighted_stake = amount_staked to match. Rewards start at zero.
+    fn from_legacy(data: &[u8], _clock: &Clock) -> Result<Self> {
+        let legacy = LegacyVaultConfig::deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(Self {
+            version: Self::CURRENT_VERSION,
+            name: legacy.name,
+            authority: legacy.authority,
+            token_mint: legacy.token_mint,
+            vault_token_account: legacy.vault_token_account,
+            goal_tokens_to_reach: legacy.goal_tokens_to_reach,
+            current_tokens_staked: legacy.current_tokens_staked,
+            goal_reached: legacy.goal_reached,
+            bump_config: legacy.bump_config,
+            bump_token_vault: legacy.bump_token_vault,
+            total_weight: legacy.current_tokens_staked,
+            ..Default::default()
+        })
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+// Lockup a staker commits to, longer locks weigh more in rewards and goal progress
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub enum LockupTier {
+    #[default]
+    Flexible, // no lock, can leave any time, 1x, doesn't count toward the goal or milestones
+    Days7,    // 1.25x
+    Days30,   // 1.5x
+    Days90,   // 2x
+}
+
+impl LockupTier {
+    const DAY: i64 = 24 * 60 * 60;
+
+    pub fn duration(self) -> i64 {
+        match self {
+            LockupTier::Flexible => 0,
+            LockupTier::Days7 => 7 * Self::DAY,
+            LockupTier::Days30 => 30 * Self::DAY,
+            LockupTier::Days90 => 90 * Self::DAY,
+        }
+    }
+
+    // Stake weight multiplier in basis points
+    pub fn weight_bps(self) -> u16 {
+        match self {
+            LockupTier::Flexible => 10_000,
+            LockupTier::Days7 => 12_500,
+            LockupTier::Days30 => 15_000,
+            LockupTier::Days90 => 20_000,
+        }
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct UserStakeInfo {
+    pub version: u8,            // Layout version, see states::version
     pub staker: Pubkey,         // The user who staked
     pub vault_config: Pubkey,   // Reference to the vault they staked in
     pub amount_staked: u64,     // Total amount this user has staked
     pub bump: u8,               // Bump seed for this UserStakeInfo PDA
+    pub reward_debt: u128,      // weighted_stake * acc_reward_per_share at the last settlement
+    pub pending_rewards: u64,   // Settled but unclaimed lamports
+    pub rewards_claimed: u64,   // Lamports claimed so far
+    pub lockup: LockupTier,     // Applies to the whole position
+    pub unlock_at: i64,         // Unix timestamp, unstaking fails before it
+    pub weighted_stake: u64,    // amount_staked times the lockup multiplier
+    pub stake_changed_slot: u64, // Slot weighted_stake last changed, see vote_weight
+    pub locked_tokens: u64,     // Share of the vault locked_tokens, amount_staked while locked and 0 otherwise
+    pub previous_weighted_stake: u64, // Checkpoint, weighted_stake held from previous_changed_slot until stake_changed_slot
+    pub previous_changed_slot: u64,
+    pub _reserved: [u64; RESERVED_WORDS - 4], // Zeroed, taken by future fields
 }
 
 impl UserStakeInfo {
-    // Calculate space: Pubkey (32*2) + u64 (8) + u8 (1) + Anchor Discriminator (8)
-    const LEN: usize = 8 + (32 * 2) + 8 + 1;
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + (32 * 2) + 8 + 1 + 16 + (8 * 2) + 1 + 8 + 8 + 8 + 8 + (8 * 2) + (RESERVED_LEN - 32);
+
+    // Locks the position for `lockup` from `now`. An active lock can be extended
+    // or moved to a longer tier, never shortened.
+    pub fn apply_lockup(&mut self, lockup: LockupTier, now: i64) -> Result<()> {
+        if now < self.unlock_at {
+            require!(lockup >= self.lockup, VaultError::LockupDowngrade);
+        }
+        self.lockup = lockup;
+        self.unlock_at = self.unlock_at.max(math::add_i64(now, lockup.duration())?);
+        Ok(())
+    }
+
+    // Unstake rules, the same before and after the vault goal:
+    //  - Flexible stake and locks that have run out can leave at any time, they never count toward the goal
+    //  - locked stake can't leave before unlock_at, the goal is reached on it so it can't pass the goal and leave
+    //  - once reached the goal stays reached, unstaking doesn't affect goal_reached
+    pub fn check_unstake(&self, amount: u64, now: i64) -> Result<()> {
+        require!(amount > 0, VaultError::RefundAmountMustBePositive);
+        require!(now >= self.unlock_at, VaultError::StakeLocked);
+        require!(self.amount_staked >= amount, VaultError::InsufficientStakeForRefund);
+        Ok(())
+    }
+
+    // Drops a lock that has run out back to Flexible at 1x, rewards earned so far are
+    // settled at the locked weight. Returns whether the position changed.
+    pub fn expire_lock(&mut self, vault: &mut VaultConfig, now: i64) -> Result<bool> {
+        if self.lockup == LockupTier::Flexible || now < self.unlock_at {
+            return Ok(false);
+        }
+        self.settle_rewards(vault.acc_reward_per_share)?;
+        self.lockup = LockupTier::Flexible;
+        self.reweigh(vault)?;
+        self.reset_reward_debt(vault.acc_reward_per_share)?;
+        Ok(true)
+    }
+
+    // Recomputes weighted_stake after amount_staked or the lockup changed
+    // and moves the difference into the vault totals. A lock counts as locked tokens
+    // until `expire_lock` drops it, stake, unstake and claims do so for their own position.
+    pub fn reweigh(&mut self, vault: &mut VaultConfig) -> Result<()> {
+        let weighted_stake = math::bps(self.amount_staked, self.lockup.weight_bps())?;
+        vault.total_weight = math::add(math::sub(vault.total_weight, self.weighted_stake)?, weighted_stake)?;
+
+        // Checkpoint the weight held until now, several changes in one slot keep the first one
+        let slot = Clock::get()?.slot;
+        if slot != self.stake_changed_slot {
+            self.previous_weighted_stake = self.weighted_stake;
+            self.previous_changed_slot = self.stake_changed_slot;
+        }
+        self.weighted_stake = weighted_stake;
+        self.stake_changed_slot = slot;
+
+        let locked_tokens = if self.lockup == LockupTier::Flexible { 0 } else { self.amount_staked };
+        vault.locked_tokens = math::add(math::sub(vault.locked_tokens, self.locked_tokens)?, locked_tokens)?;
+        self.locked_tokens = locked_tokens;
+        Ok(())
+    }
+
+    // Weight the position held at a proposal snapshotted at `snapshot_slot`, changes made
+    // in that slot or later don't count. Stake moved to another wallet after the snapshot
+    // votes with the old wallet's weight only, the new wallet held nothing then.
+    // Only the weight before the last change is kept, a position changed twice since the
+    // snapshot no longer knows its weight at it and can't vote.
+    pub fn vote_weight(&self, snapshot_slot: u64) -> u64 {
+        if self.stake_changed_slot < snapshot_slot {
+            self.weighted_stake
+        } else if self.previous_changed_slot < snapshot_slot {
+            self.previous_weighted_stake
+        } else {
+            0
+        }
+    }
+
+    fn accrued(&self, acc_reward_per_share: u128) -> Result<u128> {
+        math::mul_div(
+            self.weighted_stake as u128,
+            acc_reward_per_share,
+            VaultConfig::REWARD_PRECISION,
+        )
+    }
+
+    // Moves everything earned since the last settlement into pending_rewards.
+    // Must run before the weighted stake changes, followed by `reset_reward_debt`.
+    pub fn settle_rewards(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let earned = math::sub_u128(self.accrued(acc_reward_per_share)?, self.reward_debt)?;
+        self.pending_rewards = math::add(self.pending_rewards, math::to_u64(earned)?)?;
+        self.reward_debt = self.accrued(acc_reward_per_share)?;
+        Ok(())
+    }
+
+    pub fn reset_reward_debt(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accrued(acc_reward_per_share)?;
+        Ok(())
+    }
+}
+
+// Layout of the stakes made in the first deployment, read once by migrate_account
+#[derive(AnchorDeserialize)]
+struct LegacyUserStakeInfo {
+    staker: Pubkey,
+    vault_config: Pubkey,
+    amount_staked: u64,
+    bump: u8,
+}
+
+impl Versioned for UserStakeInfo {
+    const CURRENT_VERSION: u8 = 1;
+
+    const LEGACY_LENS: &'static [usize] = &[8 + (32 * 2) + 8 + 1];
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    // Flexible at 1x, the weight the migrated vault counts these stakes at.
+    // No reward_debt, the vault's rewards start from its own migration.
+    fn from_legacy(data: &[u8], _clock: &Clock) -> Result<Self> {
+        let legacy = LegacyUserStakeInfo::deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(Self {
+            version: Self::CURRENT_VERSION,
+            staker: legacy.staker,
+            vault_config: legacy.vault_config,
+            amount_staked: legacy.amount_staked,
+            bump: legacy.bump,
+            lockup: LockupTier::Flexible,
+            weighted_stake: legacy.amount_staked,
+            ..Default::default()
+        })
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
 }
 
 #[event]
@@ -60,4 +378,148 @@ pub enum VaultError {
     GoalCannotBeZero,
     #[msg("Vault name is invalid (empty or too long).")]
     InvalidVaultName,
-}
\ No newline at end of file
+    #[msg("Nobody is staked in this vault.")]
+    NoStakers,
+    #[msg("There are no rewards to claim.")]
+    NoRewardsToClaim,
+    #[msg("Stake is still locked.")]
+    StakeLocked,
+    #[msg("An active lockup can't be shortened.")]
+    LockupDowngrade,
+    #[msg("Milestones must be increasing, ahead of the current stake and use perks 0 to 31.")]
+    InvalidMilestones,
+    #[msg("No unlocked milestone tokens to claim.")]
+    NothingToClaim,
+    #[msg("Proposal title, uri or voting period is invalid.")]
+    InvalidProposal,
+    #[msg("Not enough stake weight to create a proposal.")]
+    InsufficientProposerWeight,
+    #[msg("Voting on this proposal is closed.")]
+    VotingClosed,
+    #[msg("Voting on this proposal hasn't ended yet.")]
+    VotingNotEnded,
+    #[msg("No stake weight to vote with at the proposal snapshot.")]
+    NoVotingPower,
+    #[msg("The stake is not locked or its lock hasn't run out yet.")]
+    LockNotExpired,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position changed at slot 100, from 500 held since slot 40 to 800
+    fn stake_info() -> UserStakeInfo {
+        UserStakeInfo {
+            weighted_stake: 800,
+            stake_changed_slot: 100,
+            previous_weighted_stake: 500,
+            previous_changed_slot: 40,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn votes_with_the_weight_held_at_the_snapshot() {
+        let stake_info = stake_info();
+        assert_eq!(stake_info.vote_weight(101), 800);
+        // Snapshots at or before the change see the checkpoint
+        assert_eq!(stake_info.vote_weight(100), 500);
+        assert_eq!(stake_info.vote_weight(41), 500);
+        // Older than the checkpoint, the weight then is unknown
+        assert_eq!(stake_info.vote_weight(40), 0);
+        assert_eq!(stake_info.vote_weight(10), 0);
+    }
+
+    #[test]
+    fn flexible_only_vault_that_never_reaches_its_goal_lets_stake_leave() {
+        let vault = VaultConfig { goal_tokens_to_reach: 1_000, current_tokens_staked: 500, ..Default::default() };
+        let stake_info = UserStakeInfo { amount_staked: 500, ..Default::default() };
+        assert!(!vault.goal_reached);
+        assert!(stake_info.check_unstake(200, 0).is_ok());
+        assert!(stake_info.check_unstake(500, 0).is_ok());
+        assert!(stake_info.check_unstake(501, 0).is_err());
+        assert!(stake_info.check_unstake(0, 0).is_err());
+    }
+
+    #[test]
+    fn locked_stake_leaves_once_its_lock_runs_out_whether_or_not_the_goal_is_reached() {
+        let mut stake_info = UserStakeInfo { amount_staked: 500, ..Default::default() };
+        stake_info.apply_lockup(LockupTier::Days30, 100).unwrap();
+        let unlock_at = stake_info.unlock_at;
+        assert!(stake_info.check_unstake(500, unlock_at - 1).is_err());
+        assert!(stake_info.check_unstake(500, unlock_at).is_ok());
+    }
+
+    #[test]
+    fn new_positions_have_no_weight_before_their_first_stake() {
+        let stake_info = UserStakeInfo {
+            weighted_stake: 800,
+            stake_changed_slot: 100,
+            ..Default::default()
+        };
+        assert_eq!(stake_info.vote_weight(50), 0);
+        assert_eq!(stake_info.vote_weight(101), 800);
+    }
+
+    // First deployment vault: name, three keys, goal, staked, goal flag and two bumps.
+    // The account was sized for a 50 char name, a shorter one leaves zeros at the end.
+    fn legacy_vault_data(authority: Pubkey) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"Moon");
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(Pubkey::new_from_array([2; 32]).as_ref());
+        data.extend_from_slice(Pubkey::new_from_array([3; 32]).as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&250u64.to_le_bytes());
+        data.extend_from_slice(&[0, 254, 253]);
+        data.resize(177 - 8, 0);
+        data
+    }
+
+    #[test]
+    fn migrates_first_deployment_vaults() {
+        let authority = Pubkey::new_unique();
+        let data = legacy_vault_data(authority);
+        assert!(VaultConfig::LEGACY_LENS.contains(&(8 + data.len())));
+        assert!(!VaultConfig::LEGACY_LENS.contains(&VaultConfig::LEN));
+
+        let vault = VaultConfig::from_legacy(&data, &Clock::default()).unwrap();
+        assert_eq!(vault.version, VaultConfig::CURRENT_VERSION);
+        assert_eq!(vault.name, "Moon");
+        assert_eq!(vault.authority, authority);
+        assert_eq!(vault.token_mint, Pubkey::new_from_array([2; 32]));
+        assert_eq!(vault.vault_token_account, Pubkey::new_from_array([3; 32]));
+        assert_eq!(vault.goal_tokens_to_reach, 1_000);
+        assert_eq!(vault.current_tokens_staked, 250);
+        assert!(!vault.goal_reached);
+        assert_eq!((vault.bump_config, vault.bump_token_vault), (254, 253));
+        // Flexible stakes at 1x
+        assert_eq!(vault.total_weight, 250);
+        assert_eq!(vault.acc_reward_per_share, 0);
+    }
+
+    #[test]
+    fn migrates_first_deployment_stakes() {
+        let staker = Pubkey::new_unique();
+        let vault_config = Pubkey::new_unique();
+        let mut data = Vec::new();
+        data.extend_from_slice(staker.as_ref());
+        data.extend_from_slice(vault_config.as_ref());
+        data.extend_from_slice(&250u64.to_le_bytes());
+        data.push(255);
+        assert!(UserStakeInfo::LEGACY_LENS.contains(&(8 + data.len())));
+        assert!(!UserStakeInfo::LEGACY_LENS.contains(&UserStakeInfo::LEN));
+
+        let stake_info = UserStakeInfo::from_legacy(&data, &Clock::default()).unwrap();
+        assert_eq!(stake_info.version, UserStakeInfo::CURRENT_VERSION);
+        assert_eq!(stake_info.staker, staker);
+        assert_eq!(stake_info.vault_config, vault_config);
+        assert_eq!(stake_info.amount_staked, 250);
+        assert_eq!(stake_info.bump, 255);
+        assert_eq!(stake_info.lockup, LockupTier::Flexible);
+        assert_eq!(stake_info.weighted_stake, 250);
+        assert_eq!(stake_info.reward_debt, 0);
+        assert_eq!(stake_info.vote_weight(1), 250);
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CommcoinError;
+
+// Accounts that outlive a program upgrade carry a layout version as their first field,
+// right after the discriminator, and reserved padding at the end.
+// New fields are carved out of the padding and the version is bumped, `migrate_account`
+// then walks existing accounts from their version up to CURRENT_VERSION one step at a time.
+pub trait Versioned: Sized {
+    const CURRENT_VERSION: u8;
+
+    // Sizes the account had in the first deployment, before the version byte was added.
+    // Accounts of one of these sizes are on version 0 and read through `from_legacy`.
+    const LEGACY_LENS: &'static [usize] = &[];
+
+    fn version(&self) -> u8;
+
+    // Upgrades the account from `version()` to `version() + 1`, bumping the version
+    fn upgrade(&mut self) -> Result<()>;
+
+    // Current account from the un-versioned layout, `data` starts after the discriminator
+    fn from_legacy(_data: &[u8], _clock: &Clock) -> Result<Self> {
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+// Offset of the version byte, right after the 8 byte discriminator
+pub const VERSION_OFFSET: usize = 8;
+
+// Zeroed space kept at the end of versioned accounts for future fields
+pub const RESERVED_WORDS: usize = 8;
+pub const RESERVED_LEN: usize = 8 * RESERVED_WORDS;
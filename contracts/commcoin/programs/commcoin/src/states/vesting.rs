@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CommcoinError,
+    math,
+    states::{Config, Versioned, RESERVED_LEN, RESERVED_WORDS},
+};
+
+// Creator allocation requested in create_coin, unix timestamps
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VestingArgs {
+    pub allocation_bps: u16, // share of the supply, capped by Config::max_creator_allocation_bps
+    pub start_at: i64,       // vesting accrues linearly from here
+    pub cliff_at: i64,       // nothing is claimable before it
+    pub end_at: i64,         // fully vested from here
+}
+
+impl VestingArgs {
+    // `launch_at` is when trading opens, vesting can't start before it.
+    // The cliff and the end are held to the config minimums from start_at.
+    pub fn validate(&self, config: &Config, launch_at: i64) -> Result<()> {
+        require!(
+            self.allocation_bps > 0
+                && self.allocation_bps <= config.max_creator_allocation_bps
+                && self.start_at >= launch_at
+                && self.cliff_at >= math::add_i64(self.start_at, config.min_vesting_cliff)?
+                && self.end_at >= math::add_i64(self.start_at, config.min_vesting_duration)?
+                && self.cliff_at <= self.end_at
+                && self.start_at < self.end_at,
+            CommcoinError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    pub fn allocation(&self, token_total_supply: u64) -> Result<u64> {
+        math::bps(token_total_supply, self.allocation_bps)
+    }
+}
+
+// Creator allocation of one coin, minted at launch into the ATA of this PDA
+// and released linearly between start_at and end_at once the cliff has passed.
+#[account]
+#[derive(Default)]
+pub struct CreatorVesting {
+    pub version: u8,          // Layout version, see states::version
+    pub mint: Pubkey,
+    pub creator: Pubkey,      // Only one who can claim
+    pub total_amount: u64,    // Minted at launch
+    pub claimed_amount: u64,  // Paid out to the creator so far
+    pub start_at: i64,
+    pub cliff_at: i64,
+    pub end_at: i64,
+    pub bump: u8,
+    pub _reserved: [u64; RESERVED_WORDS], // Zeroed, taken by future fields
+}
+
+impl CreatorVesting {
+    // 8 (discriminator) + 1 (version) + fields + reserved padding for future fields
+    pub const LEN: usize = 8 + 1 + (32 * 2) + (8 * 2) + (8 * 3) + 1 + RESERVED_LEN;
+
+    pub fn initialize(&mut self, args: &VestingArgs, mint: Pubkey, creator: Pubkey, total_amount: u64, bump: u8) {
+        self.version = Self::CURRENT_VERSION;
+        self.mint = mint;
+        self.creator = creator;
+        self.total_amount = total_amount;
+        self.claimed_amount = 0;
+        self.start_at = args.start_at;
+        self.cliff_at = args.cliff_at;
+        self.end_at = args.end_at;
+        self.bump = bump;
+    }
+
+    // Vested as of `now`, rounded down
+    pub fn vested_at(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_at {
+            return Ok(0);
+        }
+        if now >= self.end_at {
+            return Ok(self.total_amount);
+        }
+        math::to_u64(math::mul_div(
+            self.total_amount as u128,
+            (now - self.start_at) as u128,
+            (self.end_at - self.start_at) as u128,
+        )?)
+    }
+
+    pub fn claimable(&self, now: i64) -> Result<u64> {
+        math::sub(self.vested_at(now)?, self.claimed_amount)
+    }
+}
+
+impl Versioned for CreatorVesting {
+    const CURRENT_VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        // Version 1 is the first versioned layout, there is nothing to upgrade yet
+        err!(CommcoinError::UnsupportedVersion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1_000 tokens from 1_000 to 2_000, cliff at 1_250
+    fn vesting() -> CreatorVesting {
+        CreatorVesting {
+            total_amount: 1_000,
+            start_at: 1_000,
+            cliff_at: 1_250,
+            end_at: 2_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested_at(0).unwrap(), 0);
+        assert_eq!(vesting.vested_at(1_000).unwrap(), 0);
+        assert_eq!(vesting.vested_at(1_249).unwrap(), 0);
+    }
+
+    #[test]
+    fn the_cliff_releases_everything_accrued_since_the_start() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested_at(1_250).unwrap(), 250);
+        assert_eq!(vesting.vested_at(1_251).unwrap(), 251);
+        assert_eq!(vesting.vested_at(1_999).unwrap(), 999);
+    }
+
+    #[test]
+    fn everything_is_vested_from_the_end() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested_at(2_000).unwrap(), 1_000);
+        assert_eq!(vesting.vested_at(i64::MAX).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn cliff_at_the_end_vests_all_at_once() {
+        let vesting = CreatorVesting { cliff_at: 2_000, ..vesting() };
+        assert_eq!(vesting.vested_at(1_999).unwrap(), 0);
+        assert_eq!(vesting.vested_at(2_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn claimable_excludes_what_was_claimed() {
+        let vesting = CreatorVesting { claimed_amount: 250, ..vesting() };
+        assert_eq!(vesting.claimable(1_500).unwrap(), 250);
+        assert_eq!(vesting.claimable(2_000).unwrap(), 750);
+    }
+}
@@ -49,6 +49,15 @@ describe('Create Tokens', () => {
     name: 'Solana Gold',
     symbol: 'GOLDSOL',
     uri: 'https://raw.githubusercontent.com/solana-developers/program-examples/new-examples/tokens/tokens/.assets/spl-token.json',
+    vaultName: 'Stacked Community',
+    vaultGoal: new anchor.BN(80_000_000 * 1e6),
+    curveType: { constantProduct: {} },
+    launchParams: null, // platform defaults
+    initialBuySol: null,
+    antiSnipe: null, // config defaults
+    tradingStartsAt: null,
+    allowList: null,
+    creatorVesting: null, // no creator allocation, the whole supply goes to the curve
   };
 
   it('Create an SPL Token!', async () => {
@@ -59,7 +68,7 @@ describe('Create Tokens', () => {
     try {
       // SPL Token default = 9 decimals
       const create_coin_tx = await program.methods
-        .createCoin()
+        .createCoin(metadata)
         .accounts({
           signer: userKeyPair.publicKey,
           mint: mintKeypair.publicKey,
@@ -68,7 +77,7 @@ describe('Create Tokens', () => {
 
       const [curveAddress, _bumpCurveAddress] = anchor.web3.PublicKey.findProgramAddressSync(
         [
-          Buffer.from("commcoin_bonding_curve"),
+          Buffer.from("commcoin_bonding_curve"), // seeds.rs BONDING_CURVE_SEED
           mintKeypair.publicKey.toBytes()
         ],
         program.programId
@@ -78,16 +87,6 @@ describe('Create Tokens', () => {
 
 
 
-      const extend_account_tx = await program.methods
-        .extendAccount()
-        .accounts({
-          user: userKeyPair.publicKey,
-          curveAccount: curveAddress,
-        })
-        .instruction();
-      // .signers([userKeyPair, mintKeypair])
-      // .rpc();
-
       const ataForUser = await getAssociatedTokenAddress(
         mintKeypair.publicKey,
         userKeyPair.publicKey,
@@ -111,7 +110,8 @@ describe('Create Tokens', () => {
       const buy_tx = await program.methods
         .buy(
           new anchor.BN(token_to_give * 1e6),
-          new anchor.BN(lamports_per_sol * amount_used_in_sol)
+          new anchor.BN(lamports_per_sol * amount_used_in_sol),
+          [] // no allow-list proof, trading is open
         )
         .accounts({
           user: userKeyPair.publicKey,
@@ -119,11 +119,31 @@ describe('Create Tokens', () => {
         })
         .instruction();
 
+      const [vaultTokenAddress, _bumpVaultTokenAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault_tokens_v1"),
+          mintKeypair.publicKey.toBytes()
+        ],
+        program.programId
+      )
+
+      // Stake part of the bought tokens into the community vault
+      const stake_tx = await program.methods
+        .stake(new anchor.BN(1_000_000 * 1e6), { days7: {} })
+        .accounts({
+          user: userKeyPair.publicKey,
+          mint: mintKeypair.publicKey,
+          vaultTokenAccount: vaultTokenAddress,
+          userTokenAccount: ataForUser,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction();
+
       const transactions = new anchor.web3.Transaction()
         .add(create_coin_tx)
-        .add(extend_account_tx)
         .add(ataAccountInstruction)
-        .add(buy_tx);
+        .add(buy_tx)
+        .add(stake_tx);
 
       const txSignature = await provider.sendAndConfirm(transactions, [userKeyPair, mintKeypair /*, otherSignerKeypairs... */]);
 